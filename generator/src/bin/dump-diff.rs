@@ -0,0 +1,68 @@
+use anyhow::{bail, Context};
+use generator::{Dump, DumpDiff, Exclusions};
+use std::fs::File;
+use std::path::PathBuf;
+
+const USAGE: &str = "\
+Usage: dump-diff <OLD DUMP DIR> <NEW DUMP DIR> [OPTIONS]
+
+Options:
+  --json <FILE>         Also write the machine-readable report to this file
+  --exclusions <FILE>   Highlight changed types that are provided by hand-written code
+  -h, --help            Print this help";
+
+fn main() -> anyhow::Result<()> {
+    let mut dumps: Vec<PathBuf> = vec![];
+    let mut json: Option<PathBuf> = None;
+    let mut exclusions: Option<PathBuf> = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().with_context(|| format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            "--json" => json = Some(value()?.into()),
+            "--exclusions" => exclusions = Some(value()?.into()),
+            _ if arg.starts_with("--") => bail!("Unknown argument: {}\n\n{}", arg, USAGE),
+            _ => dumps.push(arg.into()),
+        }
+    }
+
+    let [old, new] = dumps.as_slice() else {
+        bail!("Expected exactly two dump directories\n\n{}", USAGE);
+    };
+
+    let old = Dump::load(old).with_context(|| format!("Unable to load {}", old.display()))?;
+    let new = Dump::load(new).with_context(|| format!("Unable to load {}", new.display()))?;
+    old.diagnostics.iter().chain(&new.diagnostics).for_each(|it| eprintln!("warning: {}", it));
+    let diff = DumpDiff::compare(&old, &new);
+
+    print!("{}", diff);
+
+    if let Some(path) = exclusions {
+        let exclusions: Exclusions = std::fs::read_to_string(&path)
+            .with_context(|| format!("Unable to read exclusions: {}", path.display()))?
+            .parse()?;
+
+        let hand_written = exclusions.types().into_iter()
+            .filter(|it| diff.structs.contains_key(*it))
+            .collect::<Vec<_>>();
+
+        if !hand_written.is_empty() {
+            println!("Hand-written types that need attention");
+            for name in hand_written {
+                println!("  {}", name);
+            }
+        }
+    }
+
+    if let Some(path) = json {
+        let file = File::create(&path).with_context(|| format!("Unable to create {}", path.display()))?;
+        serde_json::to_writer_pretty(file, &diff).context("Unable to write JSON report")?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,421 @@
+use crate::{Dump, EnumDefinition, FieldDefinition, FunctionDefinition, StructDefinition};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{Debug, Display, Formatter};
+
+/// A value that was added (`old` is `None`), removed (`new` is `None`) or changed between two dumps.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Delta<T> {
+    pub old: Option<T>,
+    pub new: Option<T>,
+}
+
+impl<T: PartialEq> Delta<T> {
+    fn between(old: Option<T>, new: Option<T>) -> Option<Self> {
+        if old == new {
+            None
+        } else {
+            Some(Self { old, new })
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffStatus {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct FieldLayout {
+    pub offset: usize,
+    pub size: usize,
+    pub bit_offset: Option<u8>,
+    pub type_name: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct FunctionSummary {
+    pub signature: String,
+    pub flags: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct StructDiff {
+    pub status: DiffStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub struct_size: Option<Delta<usize>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parents: Option<Delta<Vec<String>>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, Delta<FieldLayout>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub functions: BTreeMap<String, Delta<FunctionSummary>>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct EnumDiff {
+    pub status: DiffStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<Delta<String>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub values: BTreeMap<String, Delta<u64>>,
+}
+
+/// Differences between two dumps, e.g. before and after a game update.
+///
+/// `Display` renders a human-readable report, while `Serialize` produces the machine-readable one.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct DumpDiff {
+    pub offsets: BTreeMap<String, Delta<usize>>,
+    pub structs: BTreeMap<String, StructDiff>,
+    pub enums: BTreeMap<String, EnumDiff>,
+}
+
+impl From<&FieldDefinition> for FieldLayout {
+    fn from(value: &FieldDefinition) -> Self {
+        Self {
+            offset: value.offset,
+            size: value.size,
+            bit_offset: value.bit_offset,
+            type_name: value.signature.to_string(),
+        }
+    }
+}
+
+impl From<&FunctionDefinition> for FunctionSummary {
+    fn from(value: &FunctionDefinition) -> Self {
+        let arguments = value.arguments.iter()
+            .map(|it| format!("{}{} {}", it.type_, if it.is_out_param { "&" } else { "" }, it.name))
+            .collect::<Vec<_>>();
+
+        Self {
+            signature: format!("{}({})", value.return_value, arguments.join(", ")),
+            flags: value.flags.clone(),
+        }
+    }
+}
+
+/// Pairs up the entries of two lists by name. Duplicates keep their first occurrence.
+fn by_name<'a, T>(
+    old: impl IntoIterator<Item = &'a T>,
+    new: impl IntoIterator<Item = &'a T>,
+    name: impl Fn(&T) -> &str,
+) -> BTreeMap<&'a str, (Option<&'a T>, Option<&'a T>)>
+where
+    T: 'a,
+{
+    let mut result: BTreeMap<&str, (Option<&T>, Option<&T>)> = BTreeMap::new();
+
+    for item in old {
+        result.entry(name(item)).or_default().0.get_or_insert(item);
+    }
+
+    for item in new {
+        result.entry(name(item)).or_default().1.get_or_insert(item);
+    }
+
+    result
+}
+
+fn status<T>(old: &Option<T>, new: &Option<T>) -> DiffStatus {
+    match (old, new) {
+        (None, _) => DiffStatus::Added,
+        (_, None) => DiffStatus::Removed,
+        _ => DiffStatus::Changed,
+    }
+}
+
+impl DumpDiff {
+    pub fn compare(old: &Dump, new: &Dump) -> Self {
+        let mut result = Self::default();
+
+        for (name, (old_offset, new_offset)) in by_name(&old.offsets.data, &new.offsets.data, |it| it.0.as_str()) {
+            if let Some(delta) = Delta::between(old_offset.map(|it| it.1), new_offset.map(|it| it.1)) {
+                result.offsets.insert(name.to_string(), delta);
+            }
+        }
+
+        let old_structs = old.classes.data.iter().chain(old.structs.data.iter());
+        let new_structs = new.classes.data.iter().chain(new.structs.data.iter());
+        let empty = vec![];
+
+        for (name, (old_struct, new_struct)) in by_name(old_structs, new_structs, |it| it.name.as_str()) {
+            let old_functions = old.functions.data.get(name).unwrap_or(&empty);
+            let new_functions = new.functions.data.get(name).unwrap_or(&empty);
+
+            if let Some(diff) = Self::compare_struct(old_struct, new_struct, old_functions, new_functions) {
+                result.structs.insert(name.to_string(), diff);
+            }
+        }
+
+        for (name, (old_enum, new_enum)) in by_name(&old.enums.data, &new.enums.data, |it| it.name.as_str()) {
+            if let Some(diff) = Self::compare_enum(old_enum, new_enum) {
+                result.enums.insert(name.to_string(), diff);
+            }
+        }
+
+        result
+    }
+
+    fn compare_struct(
+        old: Option<&StructDefinition>,
+        new: Option<&StructDefinition>,
+        old_functions: &[FunctionDefinition],
+        new_functions: &[FunctionDefinition],
+    ) -> Option<StructDiff> {
+        let status = status(&old, &new);
+        let mut result = StructDiff {
+            status,
+            struct_size: Delta::between(old.map(|it| it.struct_size), new.map(|it| it.struct_size)),
+            parents: None,
+            fields: BTreeMap::new(),
+            functions: BTreeMap::new(),
+        };
+
+        // Added and removed types are only listed, their members would just be noise
+        if status != DiffStatus::Changed {
+            return Some(result);
+        }
+
+        let (old, new) = (old?, new?);
+        result.parents = Delta::between(Some(old.parents.clone()), Some(new.parents.clone()));
+
+        for (name, (old_field, new_field)) in by_name(&old.fields, &new.fields, |it| it.name.as_str()) {
+            if let Some(delta) = Delta::between(old_field.map(FieldLayout::from), new_field.map(FieldLayout::from)) {
+                result.fields.insert(name.to_string(), delta);
+            }
+        }
+
+        for (name, (old_function, new_function)) in by_name(old_functions, new_functions, |it| it.name.as_str()) {
+            if let Some(delta) = Delta::between(old_function.map(FunctionSummary::from), new_function.map(FunctionSummary::from)) {
+                result.functions.insert(name.to_string(), delta);
+            }
+        }
+
+        let unchanged = result.struct_size.is_none()
+            && result.parents.is_none()
+            && result.fields.is_empty()
+            && result.functions.is_empty();
+
+        if unchanged { None } else { Some(result) }
+    }
+
+    fn compare_enum(old: Option<&EnumDefinition>, new: Option<&EnumDefinition>) -> Option<EnumDiff> {
+        let status = status(&old, &new);
+        let mut result = EnumDiff {
+            status,
+            kind: None,
+            values: BTreeMap::new(),
+        };
+
+        if status != DiffStatus::Changed {
+            return Some(result);
+        }
+
+        let (old, new) = (old?, new?);
+        result.kind = Delta::between(Some(old.kind.to_string()), Some(new.kind.to_string()));
+
+        let old_values: HashMap<_, _> = old.options.iter().map(|(name, value)| (name.as_str(), *value)).collect();
+        let new_values: HashMap<_, _> = new.options.iter().map(|(name, value)| (name.as_str(), *value)).collect();
+        let names: BTreeSet<_> = old_values.keys().chain(new_values.keys()).collect();
+
+        for name in names {
+            if let Some(delta) = Delta::between(old_values.get(name).copied(), new_values.get(name).copied()) {
+                result.values.insert(name.to_string(), delta);
+            }
+        }
+
+        if result.kind.is_none() && result.values.is_empty() { None } else { Some(result) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty() && self.structs.is_empty() && self.enums.is_empty()
+    }
+}
+
+fn write_delta<T>(f: &mut Formatter<'_>, indent: &str, label: &str, delta: &Delta<T>, show: impl Fn(&T) -> String) -> std::fmt::Result {
+    match (&delta.old, &delta.new) {
+        (None, Some(new)) => writeln!(f, "{indent}+ {label}: {}", show(new)),
+        (Some(old), None) => writeln!(f, "{indent}- {label}: {}", show(old)),
+        (Some(old), Some(new)) => writeln!(f, "{indent}~ {label}: {} -> {}", show(old), show(new)),
+        (None, None) => Ok(()),
+    }
+}
+
+fn sigil(status: DiffStatus) -> char {
+    match status {
+        DiffStatus::Added => '+',
+        DiffStatus::Removed => '-',
+        DiffStatus::Changed => '~',
+    }
+}
+
+impl Display for FieldLayout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} @ 0x{:x}", self.type_name, self.offset)?;
+        if let Some(bit_offset) = self.bit_offset {
+            write!(f, ":{}", bit_offset)?;
+        }
+
+        write!(f, " (0x{:x} bytes)", self.size)
+    }
+}
+
+impl Display for DumpDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No differences");
+        }
+
+        if !self.offsets.is_empty() {
+            writeln!(f, "Offsets")?;
+            for (name, delta) in self.offsets.iter() {
+                write_delta(f, "  ", name, delta, |it| format!("0x{:x}", it))?;
+            }
+            writeln!(f)?;
+        }
+
+        if !self.structs.is_empty() {
+            writeln!(f, "Structs")?;
+            for (name, diff) in self.structs.iter() {
+                let size = diff.struct_size.as_ref()
+                    .and_then(|it| if diff.status == DiffStatus::Removed { it.old } else { it.new });
+
+                match (diff.status, size) {
+                    (DiffStatus::Changed, _) | (_, None) => writeln!(f, "  {} {}", sigil(diff.status), name)?,
+                    (status, Some(size)) => writeln!(f, "  {} {} (0x{:x} bytes)", sigil(status), name, size)?,
+                }
+
+                if diff.status != DiffStatus::Changed {
+                    continue;
+                }
+
+                if let Some(delta) = &diff.struct_size {
+                    write_delta(f, "      ", "size", delta, |it| format!("0x{:x}", it))?;
+                }
+
+                if let Some(delta) = &diff.parents {
+                    write_delta(f, "      ", "parents", delta, |it| it.join(", "))?;
+                }
+
+                for (field, delta) in diff.fields.iter() {
+                    write_delta(f, "      ", &format!("field {}", field), delta, |it| it.to_string())?;
+                }
+
+                for (function, delta) in diff.functions.iter() {
+                    write_delta(f, "      ", &format!("function {}", function), delta, |it| format!("{} [{}]", it.signature, it.flags))?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        if !self.enums.is_empty() {
+            writeln!(f, "Enums")?;
+            for (name, diff) in self.enums.iter() {
+                writeln!(f, "  {} {}", sigil(diff.status), name)?;
+
+                if let Some(delta) = &diff.kind {
+                    write_delta(f, "      ", "underlying type", delta, |it| it.clone())?;
+                }
+
+                for (value, delta) in diff.values.iter() {
+                    write_delta(f, "      ", value, delta, |it| it.to_string())?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DumpFormat, EnumDump, EnumKind, FieldKind, FunctionDump, Offset, OffsetData, StructDump, TypeSignature};
+
+    fn dump(struct_size: usize, field_offset: usize, function: Option<&str>, enum_value: u64, gobjects: usize) -> Dump {
+        Dump {
+            classes: StructDump {
+                data: vec![StructDefinition {
+                    name: "UMyObject".into(),
+                    parents: vec!["UObject".into()],
+                    struct_size,
+                    fields: vec![FieldDefinition::new(
+                        "Health".into(),
+                        field_offset,
+                        4,
+                        1,
+                        None,
+                        TypeSignature::new_simple("i32".into(), FieldKind::Primitive),
+                    )],
+                    package: None,
+                    functions: vec![],
+                    has_layout: true,
+                }],
+            },
+            structs: StructDump { data: vec![] },
+            enums: EnumDump {
+                data: vec![EnumDefinition {
+                    name: "EMyEnum".into(),
+                    kind: EnumKind::U8,
+                    options: vec![("First".into(), 0), ("Second".into(), enum_value)],
+                    package: None,
+                }],
+            },
+            functions: FunctionDump {
+                data: HashMap::from([(
+                    "UMyObject".to_string(),
+                    function.into_iter().map(|name| FunctionDefinition {
+                        name: name.into(),
+                        return_value: TypeSignature::new_simple("void".into(), FieldKind::Primitive),
+                        arguments: vec![],
+                        flags: "Final|Native".into(),
+                        offset: 0,
+                    }).collect(),
+                )]),
+            },
+            offsets: OffsetData {
+                data: vec![Offset("OFFSET_GOBJECTS".into(), gobjects)],
+            },
+            format: DumpFormat {
+                version: 10202,
+                marks_out_params: true,
+            },
+            diagnostics: vec![],
+        }
+    }
+
+    #[test]
+    fn test_identical_dumps() {
+        let diff = DumpDiff::compare(&dump(0x30, 0x28, Some("Tick"), 1, 0x100), &dump(0x30, 0x28, Some("Tick"), 1, 0x100));
+
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "No differences\n");
+    }
+
+    #[test]
+    fn test_changed_dumps() {
+        let diff = DumpDiff::compare(&dump(0x30, 0x28, Some("Tick"), 1, 0x100), &dump(0x38, 0x2c, None, 2, 0x200));
+
+        assert_eq!(diff.offsets["OFFSET_GOBJECTS"], Delta { old: Some(0x100), new: Some(0x200) });
+
+        let struct_diff = &diff.structs["UMyObject"];
+        assert_eq!(struct_diff.status, DiffStatus::Changed);
+        assert_eq!(struct_diff.struct_size, Some(Delta { old: Some(0x30), new: Some(0x38) }));
+        assert_eq!(struct_diff.fields["Health"].new.as_ref().map(|it| it.offset), Some(0x2c));
+        assert!(struct_diff.functions["Tick"].new.is_none());
+
+        assert_eq!(diff.enums["EMyEnum"].values["Second"], Delta { old: Some(1), new: Some(2) });
+
+        let report = diff.to_string();
+        assert!(report.contains("~ OFFSET_GOBJECTS: 0x100 -> 0x200"));
+        assert!(report.contains("- function Tick: void() [Final|Native]"));
+
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["structs"]["UMyObject"]["struct_size"]["new"], 0x38);
+    }
+}
@@ -4,17 +4,16 @@ use itertools::Itertools;
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote, ToTokens};
 use rayon::prelude::*;
-use regex::Regex;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::iter::once;
 use std::path::Path;
 use syn::{parse_str, Ident};
 
-use crate::serialization::OffsetData;
 use crate::{
-    ClassLookup, EnumDefinition, EnumDump, EnumKind, FieldDefinition, FieldKind,
-    FunctionDefinition, FunctionDump, Manifest, StructDefinition, StructDump, TypeSignature,
+    ClassLookup, Diagnostic, Dump, EnumDefinition, EnumKind, FieldDefinition, FieldKind, FunctionDefinition,
+    Manifest, Mappings, OffsetData, PackageFilter, StructDefinition, TypeSignature,
 };
 
 trait ToRustCode: Send + Sync {
@@ -23,55 +22,180 @@ trait ToRustCode: Send + Sync {
     fn generate_code(&self, context: &ClassLookup) -> TokenStream;
     fn generate_test(&self, context: &ClassLookup) -> Option<TokenStream>;
     fn generate_impl(&self, context: &ClassLookup) -> Option<TokenStream>;
+    /// Items of the package's `params` module.
+    fn generate_params(&self, context: &ClassLookup) -> Option<TokenStream>;
+    fn dropped_fields(&self, context: &ClassLookup) -> Vec<DroppedField>;
+}
+
+/// A field of the dump that has no counterpart in the generated struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DroppedField {
+    pub struct_name: String,
+    pub field_name: String,
+    pub offset: usize,
+    pub reason: DropReason,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DropReason {
+    /// The field starts before the previous field ends.
+    Overlap { previous_end: usize },
+}
+
+impl Display for DroppedField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}::{} (0x{:X}): ", self.struct_name, self.field_name, self.offset)?;
+        match self.reason {
+            DropReason::Overlap { previous_end } => {
+                write!(f, "overlaps the previous field, which ends at 0x{:X}", previous_end)
+            }
+        }
+    }
+}
+
+/// The code generated from a dump.
+#[derive(Debug, Default)]
+pub struct GeneratedCode {
+    /// Unformatted code per package, `None` being the crate root.
+    pub modules: HashMap<Option<String>, String>,
+    /// Fields that could not be represented in the generated structs, sorted by struct and offset.
+    pub dropped_fields: Vec<DroppedField>,
+    /// Every generated type and why it was generated, sorted by name.
+    pub inclusions: Vec<Inclusion>,
+    /// Entries of the dump that were skipped, in the order they were found.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl GeneratedCode {
+    /// Renders [GeneratedCode::dropped_fields] as one line per field.
+    pub fn dropped_fields_report(&self) -> String {
+        self.dropped_fields
+            .iter()
+            .map(|it| format!("{}\n", it))
+            .collect()
+    }
+
+    /// Renders [GeneratedCode::inclusions] as one line per type.
+    pub fn inclusions_report(&self) -> String {
+        self.inclusions
+            .iter()
+            .map(|it| format!("{}\n", it))
+            .collect()
+    }
+
+    /// Renders [GeneratedCode::diagnostics] as one line per entry.
+    pub fn diagnostics_report(&self) -> String {
+        self.diagnostics
+            .iter()
+            .map(|it| format!("{}\n", it))
+            .collect()
+    }
 }
 
 trait ToTokensWithContext {
     fn to_tokens(&self, context: &ClassLookup) -> TokenStream;
 }
 
+/// Why a type ended up in the generated code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InclusionReason {
+    /// Listed as a root of a pruned generation.
+    Root,
+    /// Part of a package that matched the [PackageFilter].
+    Package,
+    Parent { child: String },
+    Field { owner: String, field: String },
+    /// Used as the return value or a parameter of a function.
+    Signature { owner: String, function: String },
+}
+
+/// A type of the generated code, along with the first reason it was pulled in for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Inclusion {
+    pub type_name: String,
+    pub reason: InclusionReason,
+    /// Only generated as an opaque blob, because it is not reachable from the roots.
+    pub opaque: bool,
+}
+
+impl Display for Inclusion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.type_name)?;
+        match &self.reason {
+            InclusionReason::Root => write!(f, "root")?,
+            InclusionReason::Package => write!(f, "in a matched package")?,
+            InclusionReason::Parent { child } => write!(f, "parent of {}", child)?,
+            InclusionReason::Field { owner, field } => write!(f, "field {}::{}", owner, field)?,
+            InclusionReason::Signature { owner, function } => {
+                write!(f, "signature of {}::{}", owner, function)?
+            }
+        }
+
+        if self.opaque {
+            write!(f, " (opaque)")?;
+        }
+
+        Ok(())
+    }
+}
+
 struct TypeIterator<'a> {
     lookup: &'a ClassLookup,
-    tracked_types: HashSet<&'a str>,
+    tracked_types: HashMap<&'a str, InclusionReason>,
     struct_queue: VecDeque<&'a StructDefinition>,
     enum_queue: VecDeque<&'a EnumDefinition>,
 }
 impl<'a> TypeIterator<'a> {
     fn new(lookup: &'a ClassLookup) -> Self {
-        let mut result = Self {
+        Self {
             lookup,
-            tracked_types: HashSet::new(),
+            tracked_types: HashMap::new(),
             struct_queue: VecDeque::new(),
             enum_queue: VecDeque::new(),
-        };
+        }
+    }
 
-        for def in lookup.iter_structs() {
-            result.track_struct(def);
+    fn track_roots(&mut self, roots: &[&str]) -> anyhow::Result<()> {
+        for root in roots {
+            if self.lookup.get_struct(root).is_none() && self.lookup.get_enum(root).is_none() {
+                anyhow::bail!("Unknown root type: {}", root);
+            }
+
+            self.track_by_name(root, InclusionReason::Root);
         }
 
-        for def in lookup.iter_enums() {
-            result.track_enum(def);
+        Ok(())
+    }
+
+    fn track_packages(&mut self) {
+        for def in self.lookup.iter_structs() {
+            self.track_struct(def, InclusionReason::Package);
         }
 
-        result
+        for def in self.lookup.iter_enums() {
+            self.track_enum(def, InclusionReason::Package);
+        }
     }
 
-    fn track_struct(&mut self, def: &'a StructDefinition) {
-        if self.tracked_types.insert(&def.name) {
+    fn track_struct(&mut self, def: &'a StructDefinition, reason: InclusionReason) {
+        if !self.tracked_types.contains_key(def.name.as_str()) {
+            self.tracked_types.insert(&def.name, reason);
             self.struct_queue.push_back(def);
         }
     }
 
-    fn track_enum(&mut self, def: &'a EnumDefinition) {
-        if self.tracked_types.insert(&def.name) {
+    fn track_enum(&mut self, def: &'a EnumDefinition, reason: InclusionReason) {
+        if !self.tracked_types.contains_key(def.name.as_str()) {
+            self.tracked_types.insert(&def.name, reason);
             self.enum_queue.push_back(def);
         }
     }
 
-    fn track_by_name(&mut self, name: &str) {
+    fn track_by_name(&mut self, name: &str, reason: InclusionReason) {
         if let Some(def) = self.lookup.get_struct(name) {
-            self.track_struct(def);
+            self.track_struct(def, reason);
         } else if let Some(def) = self.lookup.get_enum(name) {
-            self.track_enum(def);
+            self.track_enum(def, reason);
         }
     }
 }
@@ -81,25 +205,33 @@ impl<'a> Iterator for TypeIterator<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(latest_struct) = self.struct_queue.pop_front() {
+            let owner = &latest_struct.name;
+
             if let Some(parent) = latest_struct.parents.first() {
-                self.track_by_name(parent.as_str());
+                self.track_by_name(parent.as_str(), InclusionReason::Parent { child: owner.clone() });
             }
 
             let mut types_queue = latest_struct
                 .fields
                 .iter()
-                .map(|it| &it.signature)
+                .map(|it| {
+                    let reason = InclusionReason::Field { owner: owner.clone(), field: it.name.clone() };
+                    (&it.signature, reason)
+                })
                 .chain(latest_struct.functions.iter().flat_map(|fun| {
-                    once(&fun.return_value).chain(fun.arguments.iter().map(|it| &it.type_))
+                    let reason = InclusionReason::Signature { owner: owner.clone(), function: fun.name.clone() };
+                    once(&fun.return_value)
+                        .chain(fun.arguments.iter().map(|it| &it.type_))
+                        .map(move |it| (it, reason.clone()))
                 }))
                 .collect::<VecDeque<_>>();
-            while let Some(type_) = types_queue.pop_front() {
+            while let Some((type_, reason)) = types_queue.pop_front() {
                 if type_.kind != FieldKind::Primitive {
-                    self.track_by_name(type_.name.as_str());
+                    self.track_by_name(type_.name.as_str(), reason.clone());
                 }
 
                 for generic in type_.generics.iter() {
-                    types_queue.push_back(generic);
+                    types_queue.push_back((generic, reason.clone()));
                 }
             }
 
@@ -112,12 +244,6 @@ impl<'a> Iterator for TypeIterator<'a> {
     }
 }
 
-impl ClassLookup {
-    fn iter_compilation_units(&self) -> impl Iterator<Item = &dyn ToRustCode> {
-        TypeIterator::new(&self)
-    }
-}
-
 #[derive(Clone, Debug)]
 enum PointerHandling {
     Raw {
@@ -263,14 +389,225 @@ impl ToRustCode for EnumDefinition {
         None
     }
 
-    fn generate_impl(&self, context: &ClassLookup) -> Option<TokenStream> {
+    fn generate_impl(&self, _context: &ClassLookup) -> Option<TokenStream> {
+        if self.name.ends_with("Flags") {
+            // flagset already provides conversions
+            return None;
+        }
+
+        let name = format_ident!("{}", self.name);
+        let enum_name = self.name.as_str();
+        let data_type = format_ident!("{}", self.kind.to_string());
+        let variants = self.options.iter().map(|(name, _)| format_ident!("{}", name)).collect::<Vec<_>>();
+        let names = self.options.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+        let values = self.options.iter().map(|(_, value)| match self.kind {
+            EnumKind::U8 => (*value as u8).to_token_stream(),
+            EnumKind::U16 => (*value as u16).to_token_stream(),
+            EnumKind::U32 => (*value as u32).to_token_stream(),
+            EnumKind::U64 => (*value).to_token_stream(),
+        });
+
+        // Some enums have a variant called ALL themselves
+        let all = if names.contains(&"ALL") {
+            format_ident!("ALL_")
+        } else {
+            format_ident!("ALL")
+        };
+
+        // Error types are spelled out, as `Self::Error` is ambiguous for enums with an `Error` variant
+        Some(quote! {
+            impl #name {
+                /// All variants in declaration order.
+                pub const #all: &'static [Self] = &[#(Self::#variants),*];
+            }
+
+            impl TryFrom<#data_type> for #name {
+                type Error = crate::UnknownEnumValue;
+
+                fn try_from(value: #data_type) -> Result<Self, crate::UnknownEnumValue> {
+                    match value {
+                        #(#values => Ok(Self::#variants),)*
+                        _ => Err(crate::UnknownEnumValue {
+                            enum_name: #enum_name,
+                            value: value as u64,
+                        }),
+                    }
+                }
+            }
+
+            impl From<#name> for #data_type {
+                fn from(value: #name) -> Self {
+                    value as #data_type
+                }
+            }
+
+            impl std::fmt::Display for #name {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(match self {
+                        #(Self::#variants => #names,)*
+                    })
+                }
+            }
+
+            impl std::str::FromStr for #name {
+                type Err = crate::UnknownEnumName;
+
+                fn from_str(s: &str) -> Result<Self, crate::UnknownEnumName> {
+                    match s {
+                        #(#names => Ok(Self::#variants),)*
+                        _ => Err(crate::UnknownEnumName {
+                            enum_name: #enum_name,
+                            name: s.to_string(),
+                        }),
+                    }
+                }
+            }
+        })
+    }
+
+    fn generate_params(&self, _context: &ClassLookup) -> Option<TokenStream> {
         None
     }
+
+    fn dropped_fields(&self, _context: &ClassLookup) -> Vec<DroppedField> {
+        vec![]
+    }
+}
+
+/// The fields of a generated struct, including the padding between them.
+struct StructLayout<'a> {
+    fields: Vec<TokenStream>,
+    /// Identifier and dumped offset of every field that was taken over from the dump
+    offsets: Vec<(Ident, usize)>,
+    bitfields: Vec<BitfieldGroup<'a>>,
+    dropped: Vec<DroppedField>,
+}
+
+/// Bitfields that are packed into the same storage.
+struct BitfieldGroup<'a> {
+    offset: usize,
+    size: usize,
+    /// The field holding the bits, `None` if the group overlaps the previous field
+    storage: Option<Ident>,
+    members: Vec<&'a FieldDefinition>,
+}
+
+impl BitfieldGroup<'_> {
+    fn storage_type(&self) -> Ident {
+        match self.size {
+            2 => format_ident!("u16"),
+            4 => format_ident!("u32"),
+            8 => format_ident!("u64"),
+            _ => format_ident!("u8"),
+        }
+    }
+
+    fn storage_doc(&self) -> String {
+        let names = self.members.iter().map(|it| format!("`{}`", it.name)).join(", ");
+        format!("Storage of the bitfields {}", names)
+    }
+
+    fn accessors(&self) -> impl Iterator<Item = TokenStream> + '_ {
+        let storage_type = self.storage_type();
+        let offset = self.offset;
+
+        // Groups without storage (e.g. placed in the tail padding of the parent) are accessed by offset
+        let (read, write) = match &self.storage {
+            Some(storage) => (quote!(self.#storage), quote!(&mut self.#storage)),
+            None => (
+                quote!(unsafe { *((std::ptr::addr_of!(*self) as *const u8).add(#offset) as *const #storage_type) }),
+                quote!(unsafe { &mut *((std::ptr::addr_of_mut!(*self) as *mut u8).add(#offset) as *mut #storage_type) }),
+            ),
+        };
+
+        self.members.iter().map(move |field| {
+            let identifier = as_identifier(field.name.as_str());
+            let getter = format_ident!("bit_get_{}", identifier);
+            let setter = format_ident!("bit_set_{}", identifier);
+            let bit_offset = field.bit_offset.unwrap_or_default() as u32;
+            let bit_width = field.bit_width as u32;
+            let doc = if bit_width == 1 {
+                format!("`{}`, bit {} at offset 0x{:X}", field.name, bit_offset, offset)
+            } else {
+                format!("`{}`, bits {}..{} at offset 0x{:X}", field.name, bit_offset, bit_offset + bit_width, offset)
+            };
+
+            if bit_width == 1 {
+                quote! {
+                    #[doc = #doc]
+                    pub fn #getter(&self) -> bool {
+                        let storage: #storage_type = #read;
+                        storage & (1 << #bit_offset) != 0
+                    }
+
+                    #[doc = #doc]
+                    pub fn #setter(&mut self, value: bool) {
+                        let storage: &mut #storage_type = #write;
+                        if value {
+                            *storage |= 1 << #bit_offset;
+                        } else {
+                            *storage &= !(1 << #bit_offset);
+                        }
+                    }
+                }
+            } else {
+                quote! {
+                    #[doc = #doc]
+                    pub fn #getter(&self) -> #storage_type {
+                        let storage: #storage_type = #read;
+                        let mask: #storage_type = (1 << #bit_width) - 1;
+                        (storage >> #bit_offset) & mask
+                    }
+
+                    #[doc = #doc]
+                    pub fn #setter(&mut self, value: #storage_type) {
+                        let storage: &mut #storage_type = #write;
+                        let mask: #storage_type = (1 << #bit_width) - 1;
+                        *storage = (*storage & !(mask << #bit_offset)) | ((value & mask) << #bit_offset);
+                    }
+                }
+            }
+        })
+    }
 }
 
 impl StructDefinition {
-    fn resolve_fields(&self, context: &ClassLookup) -> Vec<TokenStream> {
+    /// Summary of the reflection data, as the generated struct does not show the parents or the original package.
+    fn doc_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("- Size: 0x{:X}", self.struct_size)];
+        if let Some(package) = &self.package {
+            lines.push(format!("- Package: `{}`", package));
+        }
+        if !self.parents.is_empty() {
+            let parents = self.parents.iter().map(|it| format!("`{}`", it)).join(" > ");
+            lines.push(format!("- Parents: {}", parents));
+        }
+
+        lines
+    }
+
+    /// Types a delegate field with the params of its signature function, which Unreal declares on the
+    /// owning class as `<Field>__DelegateSignature` unless the signature is shared across the package.
+    fn delegate_type(&self, field: &FieldDefinition) -> Option<TokenStream> {
+        let delegate = match field.signature.name.as_str() {
+            "FMulticastInlineDelegateProperty_" => quote!(crate::TMulticastInlineDelegate),
+            "FMulticastSparseDelegateProperty_" => quote!(crate::TMulticastSparseDelegate),
+            _ => return None,
+        };
+
+        let signature_name = format!("{}__DelegateSignature", field.name);
+        let signature = self.functions.iter().find(|it| it.name == signature_name)?;
+        let params = signature.params_ident(self);
+        let lifetimes = signature.params_generics().into_iter().map(|_| quote!('static));
+
+        Some(quote!(#delegate<params::#params<#(#lifetimes),*>>))
+    }
+
+    fn resolve_fields(&self, context: &ClassLookup) -> StructLayout<'_> {
         let mut target = vec![];
+        let mut offsets = vec![];
+        let mut bitfields = vec![];
+        let mut dropped = vec![];
         let mut offset = 0;
 
         let parent = self.parents.first();
@@ -285,9 +622,34 @@ impl StructDefinition {
 
         let mut counter = self.parents.len() * 100;
 
-        for field in self.fields.iter() {
+        let mut fields = self.fields.iter().peekable();
+        while let Some(field) = fields.next() {
+            // Bitfields sharing an offset are packed into one group
+            let mut members = vec![field];
+            if field.bit_offset.is_some() {
+                while let Some(member) =
+                    fields.next_if(|it| it.bit_offset.is_some() && it.offset == field.offset)
+                {
+                    members.push(member);
+                }
+            }
+
             if offset > field.offset {
-                // Padding issue
+                dropped.extend(members.iter().map(|it| DroppedField {
+                    struct_name: self.name.clone(),
+                    field_name: it.name.clone(),
+                    offset: it.offset,
+                    reason: DropReason::Overlap { previous_end: offset },
+                }));
+
+                if field.bit_offset.is_some() {
+                    bitfields.push(BitfieldGroup {
+                        offset: field.offset,
+                        size: field.size,
+                        storage: None,
+                        members,
+                    });
+                }
                 continue;
             }
 
@@ -301,16 +663,39 @@ impl StructDefinition {
             }
 
             if field.bit_offset.is_some() {
-                // Not implemented
+                let group = BitfieldGroup {
+                    offset: field.offset,
+                    size: members.iter().map(|it| it.size).max().unwrap_or(1),
+                    storage: Some(format_ident!("_bitfield_{:x}", field.offset)),
+                    members,
+                };
+                let storage = group.storage.clone().unwrap();
+                let storage_type = group.storage_type();
+                let doc = group.storage_doc();
+
+                target.push(quote! {
+                    #[doc = #doc]
+                    pub #storage: #storage_type
+                });
+                offsets.push((storage, field.offset));
+                offset += group.size;
+                bitfields.push(group);
                 continue;
             }
 
             let field_name = as_identifier(field.name.as_str());
 
             offset = offset + field.size;
-            let field_type = &field.signature.to_tokens(context);
-
-            target.push(quote!(pub #field_name: #field_type));
+            let field_type = &self
+                .delegate_type(field)
+                .unwrap_or_else(|| field.signature.to_tokens(context));
+            let doc = format!("`{}` at offset 0x{:X}, size 0x{:X}", field.name, field.offset, field.size);
+
+            target.push(quote! {
+                #[doc = #doc]
+                pub #field_name: #field_type
+            });
+            offsets.push((field_name, field.offset));
         }
 
         if offset < self.struct_size {
@@ -319,7 +704,12 @@ impl StructDefinition {
             target.push(quote!(pub #name: [u8; #size]));
         }
 
-        target
+        StructLayout {
+            fields: target,
+            offsets,
+            bitfields,
+            dropped,
+        }
     }
 }
 
@@ -334,7 +724,7 @@ impl ToRustCode for StructDefinition {
 
     fn generate_code(&self, context: &ClassLookup) -> TokenStream {
         let name = format_ident!("{}", self.name);
-        let fields = self.resolve_fields(context);
+        let fields = self.resolve_fields(context).fields;
 
         let extend_statement = self.parents.first().map(|parent| {
             let parent_ident = format_ident!("{}", parent);
@@ -356,7 +746,10 @@ impl ToRustCode for StructDefinition {
         .into_iter()
         .map(|it| format_ident!("{}", it));
 
+        let docs = self.doc_lines();
+
         quote! {
+            #(#[doc = #docs])*
             #[repr(C)]
             #extend_statement
             #[derive(#(#derives),*)]
@@ -366,57 +759,54 @@ impl ToRustCode for StructDefinition {
         }
     }
 
-    fn generate_test(&self, _context: &ClassLookup) -> Option<TokenStream> {
+    fn generate_test(&self, context: &ClassLookup) -> Option<TokenStream> {
         let test_name = format_ident!("test_{}", self.name);
         let name = format_ident!("{}", self.name);
         let size = self.struct_size;
+        let field_assertions = self.resolve_fields(context).offsets.into_iter().map(|(field, offset)| {
+            quote! {
+                assert_eq!(offset_of!(#name, #field), #offset);
+            }
+        });
+
+        let params_tests = self.functions.iter().map(|it| it.params_test(self));
 
         Some(quote! {
             #[test]
             fn #test_name() {
                 assert_eq!(size_of::<#name>(), #size);
+                #(#field_assertions)*
             }
+
+            #(#params_tests)*
         })
     }
 
     fn generate_impl(&self, context: &ClassLookup) -> Option<TokenStream> {
         let name = format_ident!("{}", self.name);
         let functions = self.functions.iter().map(|it| it.to_tokens(self, context));
-        let bit_functions = self.fields.iter().filter(|it| it.bit_offset.is_some()).map(|field| {
-            let identifier = as_identifier(field.name.as_str());
-            let getter = format_ident!("bit_get_{}", identifier);
-            let setter = format_ident!("bit_set_{}", identifier);
-            let offset = field.offset;
-            let bit_offset = field.bit_offset.unwrap();
-   
-            quote! {
-                pub fn #getter (&self) -> bool {
-                    let base_address = unsafe {
-                        (std::ptr::addr_of!(*self) as *const u8).add(#offset)
-                    };
-                    
-                    unsafe {
-                        let mask = 0b00000001u8 << #bit_offset;
-                        ((*base_address) & mask) != 0
-                    }
-                }
+        let layout = self.resolve_fields(context);
+        let bit_functions = layout.bitfields.iter().flat_map(|group| group.accessors());
+
+        // Classes are their own ancestor, so generic helpers also accept the class they are bounded on
+        let ancestors = if self.parents.contains(&"UObject".to_string()) {
+            once(self.name.as_str())
+                .chain(self.parents.iter().map(|it| it.as_str()))
+                .map(|ancestor| {
+                    let ident = format_ident!("{}", ancestor);
+                    let package = context.get_struct(ancestor)
+                        .and_then(|it| it.package.clone())
+                        .map(|it| format_ident!("{}", it))
+                        .map(|it| quote!(#it ::));
 
-                pub fn #setter (&mut self, value: bool) {
-                    let base_address = unsafe {
-                        (std::ptr::addr_of!(*self) as *const u8).add(#offset) as *mut u8
-                    };
-        
-                    unsafe {
-                        let mask = 0b00000001u8 << #bit_offset;
-                        if value {
-                            *base_address |= mask;
-                        } else {
-                            *base_address &= !mask;
-                        }
-                    };
-                }
-            }
-        });
+                    quote! {
+                        unsafe impl crate::IsA<crate:: #package #ident> for #name {}
+                    }
+                })
+                .collect()
+        } else {
+            vec![]
+        };
 
         Some(quote! {
             impl #name {
@@ -424,27 +814,180 @@ impl ToRustCode for StructDefinition {
                 
                 #(#bit_functions)*
             }
+
+            #(#ancestors)*
+        })
+    }
+
+    fn generate_params(&self, context: &ClassLookup) -> Option<TokenStream> {
+        if self.functions.is_empty() {
+            return None;
+        }
+
+        let params = self.functions.iter().map(|it| it.params_to_tokens(self, context));
+
+        Some(quote! {
+            #(#params)*
         })
     }
+
+    fn dropped_fields(&self, context: &ClassLookup) -> Vec<DroppedField> {
+        self.resolve_fields(context).dropped
+    }
 }
 
 impl FunctionDefinition {
-    fn to_tokens(&self, owner: &StructDefinition, context: &ClassLookup) -> TokenStream {
-        let fn_id = as_identifier(self.name.as_str());
-
-        let return_value = if self.return_value.is_pointer || self.return_value.name != "void" {
+    /// The return value, `None` if the function returns `void`.
+    fn return_slot(&self) -> Option<&TypeSignature> {
+        if self.return_value.is_pointer || self.return_value.name != "void" {
             Some(&self.return_value)
         } else {
             None
-        };
+        }
+    }
+
+    /// The original name and the reflection data, which the snake-cased wrappers don't show.
+    fn doc_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("`{}`, flags `{}`", self.name, self.flags)];
+        if self.offset != 0 {
+            lines[0].push_str(format!(", exec function at offset 0x{:X}", self.offset).as_str());
+        }
+
+        if !self.arguments.is_empty() {
+            let arguments = self
+                .arguments
+                .iter()
+                .map(|it| if it.is_out_param {
+                    format!("`{}` (out)", it.name)
+                } else {
+                    format!("`{}`", it.name)
+                })
+                .join(", ");
+
+            lines.push(String::new());
+            lines.push(format!("Parameters: {}", arguments));
+        }
+
+        lines
+    }
+
+    /// Name of the params struct, e.g. `APyCharBase_GetHeroID`.
+    fn params_ident(&self, owner: &StructDefinition) -> Ident {
+        format_ident!("{}_{}", owner.name, self.name)
+    }
+
+    /// Lifetimes of the params struct: `'a` for the inputs, and one per out param that holds pointers.
+    fn params_generics(&self) -> Vec<syn::Lifetime> {
+        once("'a".to_string())
+            .chain(
+                self.arguments
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, arg)| arg.is_out_param && arg.type_.has_pointers())
+                    .map(|(index, _)| format!("'b{}", index)),
+            )
+            .map(|name| syn::Lifetime::new(name.as_str(), Span::call_site()))
+            .collect()
+    }
+
+    /// Generates the struct that `UObject::process_event` and `FFrame::locals` hold the parameters in.
+    ///
+    /// Lives in the `params` module of the owner's package.
+    fn params_to_tokens(&self, owner: &StructDefinition, context: &ClassLookup) -> TokenStream {
+        let name = self.params_ident(owner);
+        let generics = self.params_generics();
+        let class_name = &owner.name[1..];
+        let fn_name = &self.name;
+
+        let fields = self
+            .arguments
+            .iter()
+            .enumerate()
+            .map(|(index, it)| {
+                let id = as_identifier(it.name.as_str());
+                let lifetime = if it.is_out_param {
+                    format!("'b{}", index)
+                } else {
+                    "'a".to_string()
+                };
+                let type_stream = it.type_.to_tokens_with(PointerHandling::Borrow {
+                    mutable: false,
+                    lifetime: Some(lifetime),
+                }, context);
+
+                let doc = format!("`{}`", it.name);
+
+                quote! {
+                    #[doc = #doc]
+                    pub #id: #type_stream
+                }
+            })
+            .chain(self.return_slot().map(|it| {
+                let tokens = it.to_tokens(context);
+                quote!(pub return_value: #tokens)
+            }));
+        let doc = format!("Parameters of `{}::{}`", owner.name, self.name);
+
+        quote! {
+            #[doc = #doc]
+            #[repr(C)]
+            #[derive(Debug)]
+            pub struct #name<#(#generics),*> {
+                #(#fields,)*
+                pub _marker: std::marker::PhantomData<&'a u8>,
+            }
+
+            impl<#(#generics),*> crate::FunctionParams for #name<#(#generics),*> {
+                const CLASS: &'static str = #class_name;
+                const FUNCTION: &'static str = #fn_name;
+                const PARMS_SIZE: usize = ::std::mem::offset_of!(Self, _marker);
+            }
+        }
+    }
+
+    /// Checks that the params are laid out in declaration order, with the return slot last.
+    ///
+    /// The dump carries no parameter offsets, so the actual `parms_size` is compared at runtime.
+    fn params_test(&self, owner: &StructDefinition) -> TokenStream {
+        let name = self.params_ident(owner);
+        let test_name = format_ident!("test_params_{}", name);
+        let fields = self
+            .arguments
+            .iter()
+            .map(|it| as_identifier(it.name.as_str()))
+            .chain(self.return_slot().map(|_| format_ident!("return_value")))
+            .collect::<Vec<_>>();
+
+        if fields.is_empty() {
+            return quote! {
+                #[test]
+                fn #test_name() {
+                    assert_eq!(<params::#name as crate::FunctionParams>::PARMS_SIZE, 0);
+                }
+            };
+        }
+
+        quote! {
+            #[test]
+            fn #test_name() {
+                let offsets = [#(offset_of!(params::#name, #fields)),*];
+                assert!(offsets.windows(2).all(|it| it[0] < it[1]));
+                assert!(<params::#name as crate::FunctionParams>::PARMS_SIZE > offsets[offsets.len() - 1]);
+                assert!(<params::#name as crate::FunctionParams>::PARMS_SIZE <= size_of::<params::#name>());
+            }
+        }
+    }
+
+    fn to_tokens(&self, owner: &StructDefinition, context: &ClassLookup) -> TokenStream {
+        let fn_id = as_identifier(self.name.as_str());
+        let params_id = self.params_ident(owner);
+        let return_value = self.return_slot();
 
         let is_static = self.flags.contains("Static");
         let out_params = self
             .arguments
             .iter()
-            .enumerate()
-            .filter(|(_index, it)| it.is_out_param)
-            .map(|(index, it)| (index, it))
+            .filter(|it| it.is_out_param)
             .collect::<Vec<_>>();
 
         let this_arg = if !is_static {
@@ -456,7 +999,7 @@ impl FunctionDefinition {
         // Args in the function signature
         let signature_args = this_arg
             .into_iter()
-            .chain(self.arguments.iter().enumerate().map(|(index, it)| {
+            .chain(self.arguments.iter().map(|it| {
                 let id = as_identifier(it.name.as_str());
                 let type_ = &it.type_;
                 let type_stream = type_.to_tokens_with(PointerHandling::Borrow {
@@ -475,76 +1018,70 @@ impl FunctionDefinition {
                 }
             }));
 
-        // The arguments within the struct
-        let struct_args = self
-            .arguments
-            .iter()
-            .enumerate()
-            .map(|(index, it)| {
-                if it.is_out_param {
-                    let generic_name = format!("'b{}", index);
-                    let type_stream = it.type_.to_tokens_with(PointerHandling::Borrow {
-                        mutable: false,
-                        lifetime: Some(generic_name),
-                    }, context);
-                    quote!(#type_stream)
-                } else {
-                    let type_stream = it.type_.to_tokens_with(PointerHandling::Borrow {
-                        mutable: false,
-                        lifetime: Some("'a".into()),
-                    }, context);
-                    quote!(#type_stream)
-                }
-            })
-            .chain(return_value.cloned().map(|it| {
-                let tokens = it.to_tokens(context);
-                quote!(#tokens)
-            }))
-            .chain(once(quote! { std::marker::PhantomData<&'a u8> }));
-
-        // Only the names for filling the struct
-        let signature_arg_names = self
+        // Fills the params struct, out params are swapped in afterwards
+        let params_fields = self
             .arguments
             .iter()
-            .enumerate()
-            .map(|(index, it)| {
+            .map(|it| {
                 let id = as_identifier(it.name.as_str());
                 if it.is_out_param {
-                    quote!(unsafe { ::core::mem::zeroed() })
+                    quote!(#id: unsafe { ::core::mem::zeroed() })
                 } else {
                     quote!(#id)
                 }
             })
-            .chain(return_value.map(|it| quote!(unsafe { ::std::mem::zeroed() })))
-            .chain(once(quote! { std::default::Default::default() }));
+            .chain(return_value.map(|_| quote!(return_value: unsafe { ::std::mem::zeroed() })))
+            .chain(once(quote! { _marker: std::default::Default::default() }));
 
         let class_name = &owner.name[1..];
         let fn_name = &self.name;
-        let unable_to_find_class = format!("Unable to find {}", &owner.name[1..]);
-        let unable_to_find_function = format!("Unable to find {}::{}", &owner.name[1..], self.name);
 
-        let return_type = return_value.map(|it| {
-            let tokens = it.to_tokens(context);
-            quote!(-> #tokens)
-        });
+        // Appends an underscore if another function of the owner already maps to the identifier
+        let unique_ident = |id: Ident| {
+            let collides = owner
+                .functions
+                .iter()
+                .any(|it| as_identifier(it.name.as_str()) == id);
+            if collides {
+                format_ident!("{}_", id)
+            } else {
+                id
+            }
+        };
+        let try_fn_id = unique_ident(format_ident!("try_{}", fn_id));
 
-        let return_statement = return_value.map(|it| {
-            let size: syn::Index = self.arguments.len().into();
-            quote!(parms.#size)
-        });
+        let return_tokens = return_value.map(|it| it.to_tokens(context));
+        let return_type = return_tokens.as_ref().map(|it| quote!(-> #it));
+        let try_return_type = match &return_tokens {
+            Some(tokens) => quote!(-> Result<#tokens, crate::SdkError>),
+            None => quote!(-> Result<(), crate::SdkError>),
+        };
 
-        let class = if !is_static {
-            quote!(&self.class.as_ref().expect(#unable_to_find_class))
+        let return_statement = match return_value {
+            Some(_) => quote!(Ok(parms.return_value)),
+            None => quote!(Ok(())),
+        };
+
+        let class = if !is_static {
+            quote!(self.class.as_ref())
         } else {
-            quote!(UClass::find(#class_name).expect(#unable_to_find_class))
+            quote!(UClass::find(#class_name))
         };
 
-        let this = if !is_static {
-            quote!(self)
+        // Resolved before any out param is swapped, so an error leaves the arguments untouched
+        let this_statement = if is_static {
+            Some(quote! {
+                let default_object = this_class
+                    .default_object
+                    .as_ref()
+                    .ok_or(crate::SdkError::NoDefaultObject(#class_name))?;
+            })
         } else {
-            quote!(class.default_object.as_ref().expect("No default object"))
+            None
         };
 
+        let this = if !is_static { quote!(self) } else { quote!(default_object) };
+
         let call_statement = if self.flags.contains("Native") {
             quote! {
                 let flags = func.function_flags;
@@ -558,48 +1095,59 @@ impl FunctionDefinition {
             }
         };
 
-        let swap_out_into = out_params.iter().map(|(index, arg)| {
-            let accessor: syn::Index = (*index).into();
-            let arg: syn::Ident = as_identifier(arg.name.as_str());
+        let forwarded_args = self.arguments.iter().map(|it| as_identifier(it.name.as_str()));
+        let try_call = if !is_static {
+            quote!(self.#try_fn_id(#(#forwarded_args),*))
+        } else {
+            quote!(Self::#try_fn_id(#(#forwarded_args),*))
+        };
 
-            quote! {
-                std::mem::swap(&mut parms.#accessor, #arg);
-            }
-        });
+        let swap_out_into = out_params
+            .iter()
+            .map(|arg| {
+                let arg: syn::Ident = as_identifier(arg.name.as_str());
+
+                quote! {
+                    std::mem::swap(&mut parms.#arg, #arg);
+                }
+            })
+            .collect::<Vec<_>>();
 
-        let swap_out_back = out_params.iter().map(|(index, arg)| {
-            let accessor: syn::Index = (*index).into();
-            let arg: syn::Ident = as_identifier(arg.name.as_str());
+        let swap_out_back = out_params
+            .iter()
+            .map(|arg| {
+                let arg: syn::Ident = as_identifier(arg.name.as_str());
 
+                quote! {
+                    std::mem::swap(#arg, &mut parms.#arg);
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let signature_args = signature_args.collect::<Vec<_>>();
+        let params_fields = params_fields.collect::<Vec<_>>();
+
+        let body = |call_statement: TokenStream| {
             quote! {
-                std::mem::swap(#arg, &mut parms.#accessor);
-            }
-        });
+                static FUNCTION: crate::FunctionCache = crate::FunctionCache::new();
 
-        let generics = once("'a".to_string())
-            .chain(
-                out_params
-                    .iter()
-                    .filter(|(_, arg)| arg.type_.has_pointers())
-                    .map(|(index, _)| format!("'b{}", index)),
-            )
-            .map(|name| syn::Lifetime::new(name.as_str(), Span::call_site()));
+                // Not named `class`, which is a common parameter name in UE
+                let this_class = #class.ok_or(crate::SdkError::ClassNotFound(#class_name))?;
 
-        quote! {
-            pub fn #fn_id(#(#signature_args),*) #return_type {
-                #[repr(C)]
-                #[derive(Debug)]
-                struct Args<#(#generics),*>(#(#struct_args),*);
+                let func = FUNCTION
+                    .find(this_class, #fn_name)
+                    .ok_or(crate::SdkError::FunctionNotFound {
+                        class: #class_name,
+                        function: #fn_name,
+                    })?;
 
-                let class = #class;
+                <params::#params_id as crate::FunctionParams>::check(func)?;
 
-                let func = class
-                    .find_function_mut(#fn_name)
-                    .expect(#unable_to_find_function);
+                #this_statement
 
-                let mut parms = Args(
-                    #(#signature_arg_names),*
-                );
+                let mut parms = params::#params_id {
+                    #(#params_fields),*
+                };
 
                 #(#swap_out_into)*
 
@@ -609,50 +1157,224 @@ impl FunctionDefinition {
 
                 #return_statement
             }
+        };
+
+        let try_body = body(call_statement);
+        let docs = self.doc_lines();
+
+        // Calls the exec thunk directly, which skips the vtable dispatch and the flag juggling of process_event
+        let native_fn = if self.flags.contains("Native") && self.offset != 0 {
+            let native_fn_id = unique_ident(format_ident!("try_{}_native", fn_id));
+            let offset = self.offset;
+            let native_body = body(quote! {
+                #this.call_native(func, #offset, &mut parms)?;
+            });
+
+            Some(quote! {
+                #(#[doc = #docs])*
+                ///
+                /// # Safety
+                /// The exec thunk runs on a synthesized frame, see `UObject::call_native`.
+                #[cfg(feature = "native_calls")]
+                pub unsafe fn #native_fn_id(#(#signature_args),*) #try_return_type {
+                    #native_body
+                }
+            })
+        } else {
+            None
+        };
+
+        quote! {
+            #(#[doc = #docs])*
+            pub fn #try_fn_id(#(#signature_args),*) #try_return_type {
+                #try_body
+            }
+
+            #native_fn
+
+            #(#[doc = #docs])*
+            pub fn #fn_id(#(#signature_args),*) #return_type {
+                #try_call.unwrap_or_else(|err| panic!("{}", err))
+            }
         }
     }
 }
 
+/// Loads the package manifest of a dump.
+///
+/// Prefers the full `GObjects-Dump.txt` and falls back to the compact `Manifest.json`.
+/// Returns `None` if the dump contains neither.
+pub fn load_manifest<P: AsRef<Path>>(base_path: P) -> anyhow::Result<Option<Manifest>> {
+    let objects_dump = base_path.as_ref().join("GObjects-Dump.txt");
+    let compact_manifest = base_path.as_ref().join("Manifest.json");
+
+    if objects_dump.exists() {
+        let manifest = std::fs::read_to_string(objects_dump)
+            .context("ObjectsDump")?
+            .parse()
+            .context("Unable to parse manifest")?;
+
+        Ok(Some(manifest))
+    } else if compact_manifest.exists() {
+        Ok(Some(Manifest::from_json(File::open(compact_manifest).context("Manifest")?)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Loads a dump and assigns its types to their packages.
+///
+/// `base_path` can also be a `.usmap` file, whose types are only assigned to packages
+/// if a `Manifest.json` sits next to it (apart from blueprint classes).
+pub(crate) fn load_lookup<P: AsRef<Path>>(
+    base_path: P,
+    package_filter: PackageFilter,
+) -> anyhow::Result<(ClassLookup, OffsetData)> {
+    if base_path.as_ref().extension().is_some_and(|it| it == "usmap") {
+        let Mappings { structs, enums, diagnostics } = Mappings::load(base_path.as_ref())?;
+        let manifest = match base_path.as_ref().parent() {
+            Some(directory) => load_manifest(directory)?,
+            None => None,
+        };
+
+        let manifest = match manifest {
+            Some(mut manifest) => {
+                manifest.infer_blueprints(&[&structs]);
+                manifest
+            }
+            None => Manifest::infer(&[&structs]),
+        };
+
+        let mut lut = ClassLookup::new(manifest, package_filter);
+        lut.add_diagnostics(diagnostics);
+        lut.add_struct_dump(structs);
+        lut.add_enum_dump(enums);
+
+        return Ok((lut, OffsetData { data: vec![] }));
+    }
+
+    let Dump { classes, structs, enums, functions, offsets, diagnostics, .. } = Dump::load(base_path.as_ref())?;
+
+    let manifest = match load_manifest(base_path.as_ref())? {
+        Some(mut manifest) => {
+            manifest.infer_blueprints(&[&classes, &structs]);
+            manifest
+        }
+        None => {
+            eprintln!("Neither GObjects-Dump.txt nor Manifest.json found, only blueprint packages can be inferred");
+            Manifest::infer(&[&classes, &structs])
+        }
+    };
+
+    let mut lut = ClassLookup::new(manifest, package_filter);
+    lut.add_diagnostics(diagnostics);
+    lut.add_struct_dump(classes);
+    lut.add_struct_dump(structs);
+    lut.resolve_qualified_parents();
+    lut.add_enum_dump(enums);
+    lut.add_function_dump(functions);
+
+    Ok((lut, offsets))
+}
+
+/// Turns every struct that is not reachable from `roots` into an opaque blob.
+///
+/// Returns the reachable types along with the reason they were reached, `None` if there are no roots.
+fn prune(lut: &mut ClassLookup, roots: &[&str]) -> anyhow::Result<Option<HashMap<String, InclusionReason>>> {
+    if roots.is_empty() {
+        return Ok(None);
+    }
+
+    let mut iterator = TypeIterator::new(lut);
+    iterator.track_roots(roots)?;
+    iterator.by_ref().for_each(drop);
+
+    let closure: HashMap<_, _> = iterator
+        .tracked_types
+        .into_iter()
+        .map(|(name, reason)| (name.to_string(), reason))
+        .collect();
+
+    lut.make_opaque(|name| closure.contains_key(name));
+
+    Ok(Some(closure))
+}
+
+fn collect_inclusions(
+    tracked_types: HashMap<&str, InclusionReason>,
+    closure: Option<&HashMap<String, InclusionReason>>,
+    lut: &ClassLookup,
+    excluded_types: &[&str],
+) -> Vec<Inclusion> {
+    let mut inclusions: Vec<_> = tracked_types
+        .into_iter()
+        .filter(|(name, _)| !excluded_types.contains(name))
+        .map(|(name, reason)| match closure {
+            // The closure knows the actual reason, whereas the packages are tracked before anything the roots reference
+            Some(closure) => match closure.get(name) {
+                Some(reason) => Inclusion { type_name: name.to_string(), reason: reason.clone(), opaque: false },
+                None => Inclusion { type_name: name.to_string(), reason, opaque: lut.get_struct(name).is_some() },
+            },
+            None => Inclusion { type_name: name.to_string(), reason, opaque: false },
+        })
+        .collect();
+    inclusions.sort_by(|a, b| a.type_name.cmp(&b.type_name));
+
+    inclusions
+}
+
+/// Generates the code of the packages matching `package_filter`, plus the types they depend on.
+///
+/// If `roots` is not empty, only the types reachable from them through parents, fields and function signatures
+/// are generated in full. The remaining types are reduced to opaque blobs of the right size, which keeps them
+/// nameable while skipping their fields, functions and everything the latter would pull in.
 pub fn generate_code<P: AsRef<Path>>(
     base_path: P,
     excluded_types: &[&str],
-    package_filter: Option<Regex>,
-) -> anyhow::Result<HashMap<Option<String>, String>> {
-    let manifest: Manifest = std::fs::read_to_string(base_path.as_ref().join("GObjects-Dump.txt"))
-        .context("ObjectsDump")?
-        .parse()
-        .context("Unable to parse manifest")?;
-    let structs_dump: StructDump = StructDump::from_raw_json(
-        File::open(base_path.as_ref().join("StructsInfo.json")).context("StructsInfo")?,
-    )?;
-    let classes_dump: StructDump = StructDump::from_raw_json(
-        File::open(base_path.as_ref().join("ClassesInfo.json")).context("ClassesInfo")?,
-    )?;
-    let enums_dump: EnumDump = EnumDump::from_raw_json(
-        File::open(base_path.as_ref().join("EnumsInfo.json")).context("EnumsInfo")?,
-    )?;
-    let offsets: OffsetData = serde_json::from_reader(
-        File::open(base_path.as_ref().join("OffsetsInfo.json")).context("Offsets")?,
-    )?;
+    roots: &[&str],
+    package_filter: PackageFilter,
+) -> anyhow::Result<GeneratedCode> {
+    let (mut lut, offsets) = load_lookup(base_path, package_filter)?;
+
+    // Generating structs without offsets would only produce garbage, so `.usmap` mappings yield enums only
+    let without_layout = lut.remove_structs_without_layout();
+    if without_layout > 0 {
+        lut.add_diagnostics([Diagnostic::new(
+            "<mappings>",
+            None,
+            format!("{} structs have no layout and are not generated", without_layout),
+        )]);
+    }
 
-    let mut lut = ClassLookup::new(manifest, package_filter);
-    lut.add_struct_dump(classes_dump);
-    lut.add_struct_dump(structs_dump);
-    lut.add_enum_dump(enums_dump);
+    let closure = prune(&mut lut, roots)?;
 
-    lut.add_function_dump(FunctionDump::from_raw_json(
-        File::open(base_path.as_ref().join("FunctionsInfo.json")).context("Functions")?,
-    )?);
+    let mut iterator = TypeIterator::new(&lut);
+    iterator.track_roots(roots)?;
+    iterator.track_packages();
 
-    let mut grouped: HashMap<_, Vec<&dyn ToRustCode>> = lut
-        .iter_compilation_units()
+    let mut grouped: HashMap<_, Vec<&dyn ToRustCode>> = iterator
+        .by_ref()
         .filter(|it| !excluded_types.contains(&it.name()))
         .into_grouping_map_by(|it| it.package())
         .collect();
 
+    let inclusions = collect_inclusions(iterator.tracked_types, closure.as_ref(), &lut, excluded_types);
+
     grouped.entry(None).or_insert(Vec::new());
 
-    let definitions: HashMap<_, _> = grouped.par_iter().map(|(package, structs)| {
+    // The iteration order of the lookup differs between runs, but unchanged packages must yield identical code
+    for types in grouped.values_mut() {
+        types.sort_by(|a, b| a.name().cmp(b.name()));
+    }
+
+    let mut dropped_fields: Vec<_> = grouped
+        .values()
+        .flatten()
+        .flat_map(|it| it.dropped_fields(&lut))
+        .collect();
+    dropped_fields.sort_by(|a, b| (&a.struct_name, a.offset).cmp(&(&b.struct_name, b.offset)));
+
+    let modules: HashMap<_, _> = grouped.par_iter().map(|(package, structs)| {
         let code = structs.iter().map(|it| {
             let code = it.generate_code(&lut);
             let implem = it.generate_impl(&lut);
@@ -666,6 +1388,20 @@ pub fn generate_code<P: AsRef<Path>>(
 
         let tests = structs.iter().filter_map(|it| it.generate_test(&lut));
 
+        let params = structs.iter().filter_map(|it| it.generate_params(&lut)).collect::<Vec<_>>();
+        let params_module = if params.is_empty() {
+            None
+        } else {
+            Some(quote! {
+                pub mod params {
+                    #![allow(non_camel_case_types)]
+                    use super::*;
+
+                    #(#params)*
+                }
+            })
+        };
+
         let offsets = if package.is_none() {
             let offset_constants = offsets.data.iter().map(|offset| {
                 let ident = format_ident!("{}", offset.0);
@@ -692,10 +1428,12 @@ pub fn generate_code<P: AsRef<Path>>(
 
             #(#code)*
 
+            #params_module
+
             #[cfg(test)]
             mod tests {
                 #![allow(non_snake_case)]
-                use std::mem::size_of;
+                use std::mem::{offset_of, size_of};
                 use super::*;
 
                 #(#tests)*
@@ -705,16 +1443,21 @@ pub fn generate_code<P: AsRef<Path>>(
         (package.map(|it| it.to_string()), code.to_string())
     }).collect();
 
-    Ok(definitions)
+    Ok(GeneratedCode {
+        modules,
+        dropped_fields,
+        inclusions,
+        diagnostics: lut.diagnostics().to_vec(),
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
 
     use rust_format::{Formatter, PrettyPlease};
 
-    use crate::{EnumKind, FieldKind};
+    use crate::{ArgumentDefinition, EnumKind, FieldKind, StructDump};
 
     use super::*;
 
@@ -732,7 +1475,7 @@ mod tests {
                 packages: HashSet::new(),
                 structs: HashMap::new(),
             },
-            None,
+            PackageFilter::default(),
         );
 
         let tokens = def.generate_code(&lookup);
@@ -751,6 +1494,82 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_enum_impl() {
+        let def = EnumDefinition {
+            kind: EnumKind::U8,
+            name: "EMyTest".into(),
+            package: None,
+            options: vec![("Option1".into(), 0), ("Option2".into(), 2)],
+        };
+
+        let lookup = ClassLookup::new(
+            Manifest {
+                packages: HashSet::new(),
+                structs: HashMap::new(),
+            },
+            PackageFilter::default(),
+        );
+
+        let tokens = def.generate_impl(&lookup).unwrap();
+        let actual = PrettyPlease::default().format_tokens(tokens).unwrap();
+        let expected = PrettyPlease::default()
+            .format_tokens(quote! {
+                impl EMyTest {
+                    /// All variants in declaration order.
+                    pub const ALL: &'static [Self] = &[Self::Option1, Self::Option2];
+                }
+
+                impl TryFrom<u8> for EMyTest {
+                    type Error = crate::UnknownEnumValue;
+
+                    fn try_from(value: u8) -> Result<Self, crate::UnknownEnumValue> {
+                        match value {
+                            0u8 => Ok(Self::Option1),
+                            2u8 => Ok(Self::Option2),
+                            _ => Err(crate::UnknownEnumValue {
+                                enum_name: "EMyTest",
+                                value: value as u64,
+                            }),
+                        }
+                    }
+                }
+
+                impl From<EMyTest> for u8 {
+                    fn from(value: EMyTest) -> Self {
+                        value as u8
+                    }
+                }
+
+                impl std::fmt::Display for EMyTest {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        f.write_str(match self {
+                            Self::Option1 => "Option1",
+                            Self::Option2 => "Option2",
+                        })
+                    }
+                }
+
+                impl std::str::FromStr for EMyTest {
+                    type Err = crate::UnknownEnumName;
+
+                    fn from_str(s: &str) -> Result<Self, crate::UnknownEnumName> {
+                        match s {
+                            "Option1" => Ok(Self::Option1),
+                            "Option2" => Ok(Self::Option2),
+                            _ => Err(crate::UnknownEnumName {
+                                enum_name: "EMyTest",
+                                name: s.to_string(),
+                            }),
+                        }
+                    }
+                }
+            })
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn test_struct() {
         let def = StructDefinition {
@@ -777,6 +1596,7 @@ mod tests {
                 ),
             ],
             functions: vec![],
+            has_layout: true,
         };
 
         let lookup = ClassLookup::new(
@@ -784,18 +1604,21 @@ mod tests {
                 packages: HashSet::new(),
                 structs: HashMap::new(),
             },
-            None,
+            PackageFilter::default(),
         );
         let tokens = def.generate_code(&lookup);
 
         let actual = PrettyPlease::default().format_tokens(tokens).unwrap();
         let expected = PrettyPlease::default()
             .format_tokens(quote! {
+                #[doc = "- Size: 0x14"]
                 #[repr(C)]
                 #[derive(Debug, Clone)]
                 pub struct MyTest {
+                    #[doc = "`field_1` at offset 0x0, size 0x1"]
                     pub field_1: u8,
                     pub _padding_0: [u8; 7usize],
+                    #[doc = "`field_2` at offset 0x8, size 0x8"]
                     pub field_2: *mut u8,
                     pub _padding_1: [u8; 4usize]
                 }
@@ -804,6 +1627,608 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_class_ancestors() {
+        let def = StructDefinition {
+            struct_size: 40,
+            package: None,
+            name: "AMyActor".into(),
+            parents: vec!["AActor".into(), "UObject".into()],
+            fields: vec![],
+            functions: vec![],
+            has_layout: true,
+        };
+
+        let lookup = ClassLookup::new(
+            Manifest {
+                packages: HashSet::new(),
+                structs: HashMap::new(),
+            },
+            PackageFilter::default(),
+        );
+        assert_eq!(
+            def.doc_lines(),
+            vec!["- Size: 0x28".to_string(), "- Parents: `AActor` > `UObject`".to_string()]
+        );
+
+        let tokens = def.generate_impl(&lookup).unwrap();
+
+        let actual = PrettyPlease::default().format_tokens(tokens).unwrap();
+        let expected = PrettyPlease::default()
+            .format_tokens(quote! {
+                impl AMyActor {}
+
+                unsafe impl crate::IsA<crate::AMyActor> for AMyActor {}
+                unsafe impl crate::IsA<crate::AActor> for AMyActor {}
+                unsafe impl crate::IsA<crate::UObject> for AMyActor {}
+            })
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_delegates() {
+        let delegate = |name: &str, offset: usize, type_name: &str| {
+            FieldDefinition::new(
+                name.into(),
+                offset,
+                0x10,
+                1,
+                None,
+                TypeSignature::new_simple(type_name.into(), FieldKind::Primitive),
+            )
+        };
+        let def = StructDefinition {
+            struct_size: 0x30,
+            package: None,
+            name: "UMyComponent".into(),
+            parents: vec![],
+            fields: vec![
+                delegate("OnChanged", 0x0, "FMulticastInlineDelegateProperty_"),
+                delegate("OnShared", 0x10, "FMulticastInlineDelegateProperty_"),
+                delegate("OnHit", 0x20, "FMulticastSparseDelegateProperty_"),
+            ],
+            functions: vec![FunctionDefinition {
+                name: "OnChanged__DelegateSignature".into(),
+                return_value: TypeSignature::new_simple("void".into(), FieldKind::Primitive),
+                arguments: vec![ArgumentDefinition {
+                    name: "Value".into(),
+                    type_: TypeSignature::new_simple("int32".into(), FieldKind::Primitive),
+                    is_out_param: false,
+                }],
+                flags: "MulticastDelegate|Public|Delegate".into(),
+                offset: 0,
+            }],
+            has_layout: true,
+        };
+
+        let lookup = ClassLookup::new(
+            Manifest {
+                packages: HashSet::new(),
+                structs: HashMap::new(),
+            },
+            PackageFilter::default(),
+        );
+        let fields = def.resolve_fields(&lookup).fields;
+
+        let actual = PrettyPlease::default().format_tokens(quote!(struct UMyComponent { #(#fields),* })).unwrap();
+        let expected = PrettyPlease::default()
+            .format_tokens(quote! {
+                struct UMyComponent {
+                    #[doc = "`OnChanged` at offset 0x0, size 0x10"]
+                    pub on_changed: crate::TMulticastInlineDelegate<params::UMyComponent_OnChanged__DelegateSignature<'static>>,
+                    #[doc = "`OnShared` at offset 0x10, size 0x10"]
+                    pub on_shared: FMulticastInlineDelegateProperty_,
+                    #[doc = "`OnHit` at offset 0x20, size 0x10"]
+                    pub on_hit: FMulticastSparseDelegateProperty_
+                }
+            })
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_pruned() {
+        let u8_signature = TypeSignature::new_simple("u8".into(), FieldKind::Primitive);
+        let class = |name: &str, fields: Vec<FieldDefinition>| StructDefinition {
+            struct_size: 0x30,
+            package: None,
+            name: name.into(),
+            parents: vec!["UObject".into()],
+            fields,
+            functions: vec![],
+            has_layout: true,
+        };
+
+        let mut root = class("ARoot", vec![]);
+        root.functions.push(FunctionDefinition {
+            name: "GetUsed".into(),
+            return_value: TypeSignature::new_pointer("AUsed".into(), FieldKind::Class),
+            arguments: vec![],
+            flags: "Final|Native|Public".into(),
+            offset: 0,
+        });
+
+        let mut lut = ClassLookup::new(
+            Manifest {
+                packages: HashSet::from(["Game".to_string()]),
+                structs: HashMap::from([
+                    ("Object".to_string(), "Game".to_string()),
+                    ("Root".to_string(), "Game".to_string()),
+                    ("Used".to_string(), "Game".to_string()),
+                    ("Unused".to_string(), "Game".to_string()),
+                ]),
+            },
+            PackageFilter::default(),
+        );
+        lut.add_struct_dump(StructDump {
+            data: vec![
+                StructDefinition {
+                    struct_size: 0x28,
+                    parents: vec![],
+                    ..class("UObject", vec![])
+                },
+                root,
+                class("AUsed", vec![FieldDefinition::new("Value".into(), 0x28, 1, 1, None, u8_signature.clone())]),
+                class("AUnused", vec![FieldDefinition::new("Value".into(), 0x28, 1, 1, None, u8_signature)]),
+            ],
+        });
+
+        let closure = prune(&mut lut, &["ARoot"]).unwrap().unwrap();
+        assert_eq!(closure.len(), 3);
+        assert_eq!(closure["AUsed"], InclusionReason::Signature { owner: "ARoot".into(), function: "GetUsed".into() });
+        assert_eq!(lut.get_struct("AUsed").unwrap().fields.len(), 1);
+        assert!(lut.get_struct("AUnused").unwrap().fields.is_empty());
+        assert!(prune(&mut lut, &["AMissing"]).is_err());
+
+        let mut iterator = TypeIterator::new(&lut);
+        iterator.track_roots(&["ARoot"]).unwrap();
+        iterator.track_packages();
+        iterator.by_ref().for_each(drop);
+
+        let report = collect_inclusions(iterator.tracked_types, Some(&closure), &lut, &["UObject"])
+            .iter()
+            .map(|it| it.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            report,
+            vec![
+                "ARoot: root",
+                "AUnused: in a matched package (opaque)",
+                "AUsed: signature of ARoot::GetUsed",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_struct_layout() {
+        let u8_signature = TypeSignature::new_simple("u8".into(), FieldKind::Primitive);
+        let def = StructDefinition {
+            struct_size: 16,
+            package: None,
+            name: "MyTest".into(),
+            parents: vec![],
+            fields: vec![
+                FieldDefinition::new("field_1".into(), 0, 1, 1, None, u8_signature.clone()),
+                FieldDefinition::new("bFlag".into(), 1, 1, 1, Some(0), u8_signature.clone()),
+                FieldDefinition::new(
+                    "field_2".into(),
+                    8,
+                    8,
+                    1,
+                    None,
+                    TypeSignature::new_pointer("u8".into(), FieldKind::Primitive),
+                ),
+                FieldDefinition::new("field_3".into(), 12, 1, 1, None, u8_signature),
+            ],
+            functions: vec![],
+            has_layout: true,
+        };
+
+        let lookup = ClassLookup::new(
+            Manifest {
+                packages: HashSet::new(),
+                structs: HashMap::new(),
+            },
+            PackageFilter::default(),
+        );
+
+        let actual = PrettyPlease::default().format_tokens(def.generate_test(&lookup).unwrap()).unwrap();
+        let expected = PrettyPlease::default()
+            .format_tokens(quote! {
+                #[test]
+                fn test_MyTest() {
+                    assert_eq!(size_of::<MyTest>(), 16usize);
+                    assert_eq!(offset_of!(MyTest, field_1), 0usize);
+                    assert_eq!(offset_of!(MyTest, _bitfield_1), 1usize);
+                    assert_eq!(offset_of!(MyTest, field_2), 8usize);
+                }
+            })
+            .unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(
+            def.dropped_fields(&lookup),
+            vec![
+                DroppedField {
+                    struct_name: "MyTest".into(),
+                    field_name: "field_3".into(),
+                    offset: 12,
+                    reason: DropReason::Overlap { previous_end: 16 },
+                },
+            ]
+        );
+        assert_eq!(
+            def.dropped_fields(&lookup)[0].to_string(),
+            "MyTest::field_3 (0xC): overlaps the previous field, which ends at 0x10"
+        );
+    }
+
+    #[test]
+    fn test_bitfields() {
+        let u8_signature = TypeSignature::new_simple("u8".into(), FieldKind::Primitive);
+        let mut mode = FieldDefinition::new("Mode".into(), 1, 1, 1, Some(2), u8_signature.clone());
+        mode.bit_width = 3;
+
+        let def = StructDefinition {
+            struct_size: 4,
+            package: None,
+            name: "MyTest".into(),
+            parents: vec![],
+            fields: vec![
+                FieldDefinition::new("field_1".into(), 0, 1, 1, None, u8_signature.clone()),
+                FieldDefinition::new("bFlag".into(), 1, 1, 1, Some(0), u8_signature.clone()),
+                mode,
+                FieldDefinition::new("field_2".into(), 2, 1, 1, None, u8_signature),
+            ],
+            functions: vec![],
+            has_layout: true,
+        };
+
+        let lookup = ClassLookup::new(
+            Manifest {
+                packages: HashSet::new(),
+                structs: HashMap::new(),
+            },
+            PackageFilter::default(),
+        );
+
+        let tokens = def.generate_code(&lookup);
+        let implementation = def.generate_impl(&lookup).unwrap();
+        let actual = PrettyPlease::default().format_tokens(quote!(#tokens #implementation)).unwrap();
+        let expected = PrettyPlease::default()
+            .format_tokens(quote! {
+                #[doc = "- Size: 0x4"]
+                #[repr(C)]
+                #[derive(Debug, Clone)]
+                pub struct MyTest {
+                    #[doc = "`field_1` at offset 0x0, size 0x1"]
+                    pub field_1: u8,
+                    #[doc = "Storage of the bitfields `bFlag`, `Mode`"]
+                    pub _bitfield_1: u8,
+                    #[doc = "`field_2` at offset 0x2, size 0x1"]
+                    pub field_2: u8,
+                    pub _padding_0: [u8; 1usize]
+                }
+
+                impl MyTest {
+                    #[doc = "`bFlag`, bit 0 at offset 0x1"]
+                    pub fn bit_get_b_flag(&self) -> bool {
+                        let storage: u8 = self._bitfield_1;
+                        storage & (1 << 0u32) != 0
+                    }
+
+                    #[doc = "`bFlag`, bit 0 at offset 0x1"]
+                    pub fn bit_set_b_flag(&mut self, value: bool) {
+                        let storage: &mut u8 = &mut self._bitfield_1;
+                        if value {
+                            *storage |= 1 << 0u32;
+                        } else {
+                            *storage &= !(1 << 0u32);
+                        }
+                    }
+
+                    #[doc = "`Mode`, bits 2..5 at offset 0x1"]
+                    pub fn bit_get_mode(&self) -> u8 {
+                        let storage: u8 = self._bitfield_1;
+                        let mask: u8 = (1 << 3u32) - 1;
+                        (storage >> 2u32) & mask
+                    }
+
+                    #[doc = "`Mode`, bits 2..5 at offset 0x1"]
+                    pub fn bit_set_mode(&mut self, value: u8) {
+                        let storage: &mut u8 = &mut self._bitfield_1;
+                        let mask: u8 = (1 << 3u32) - 1;
+                        *storage = (*storage & !(mask << 2u32)) | ((value & mask) << 2u32);
+                    }
+                }
+            })
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_function() {
+        let owner = StructDefinition {
+            struct_size: 40,
+            package: None,
+            name: "UMyLibrary".into(),
+            parents: vec!["UObject".into()],
+            fields: vec![],
+            functions: vec![],
+            has_layout: true,
+        };
+
+        let def = FunctionDefinition {
+            name: "GetValue".into(),
+            return_value: TypeSignature::new_simple("i32".into(), FieldKind::Primitive),
+            arguments: vec![],
+            flags: "Final|Static".into(),
+            offset: 0,
+        };
+
+        let lookup = ClassLookup::new(
+            Manifest {
+                packages: HashSet::new(),
+                structs: HashMap::new(),
+            },
+            PackageFilter::default(),
+        );
+
+        let tokens = def.to_tokens(&owner, &lookup);
+        let actual = PrettyPlease::default().format_tokens(quote!(impl UMyLibrary { #tokens })).unwrap();
+        let expected = PrettyPlease::default()
+            .format_tokens(quote! {
+                impl UMyLibrary {
+                    #[doc = "`GetValue`, flags `Final|Static`"]
+                    pub fn try_get_value() -> Result<i32, crate::SdkError> {
+                        static FUNCTION: crate::FunctionCache = crate::FunctionCache::new();
+
+                        let this_class = UClass::find("MyLibrary").ok_or(crate::SdkError::ClassNotFound("MyLibrary"))?;
+
+                        let func = FUNCTION
+                            .find(this_class, "GetValue")
+                            .ok_or(crate::SdkError::FunctionNotFound {
+                                class: "MyLibrary",
+                                function: "GetValue",
+                            })?;
+
+                        <params::UMyLibrary_GetValue as crate::FunctionParams>::check(func)?;
+
+                        let default_object = this_class
+                            .default_object
+                            .as_ref()
+                            .ok_or(crate::SdkError::NoDefaultObject("MyLibrary"))?;
+
+                        let mut parms = params::UMyLibrary_GetValue {
+                            return_value: unsafe { ::std::mem::zeroed() },
+                            _marker: std::default::Default::default(),
+                        };
+
+                        default_object.process_event(func, &mut parms);
+
+                        Ok(parms.return_value)
+                    }
+
+                    #[doc = "`GetValue`, flags `Final|Static`"]
+                    pub fn get_value() -> i32 {
+                        Self::try_get_value().unwrap_or_else(|err| panic!("{}", err))
+                    }
+                }
+            })
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_function_native() {
+        let owner = StructDefinition {
+            struct_size: 40,
+            package: None,
+            name: "APyCharBase".into(),
+            parents: vec!["UObject".into()],
+            fields: vec![],
+            functions: vec![],
+            has_layout: true,
+        };
+
+        let def = FunctionDefinition {
+            name: "Jump".into(),
+            return_value: TypeSignature::new_simple("void".into(), FieldKind::Primitive),
+            arguments: vec![],
+            flags: "Final|Native|Public".into(),
+            offset: 0x1234,
+        };
+
+        let lookup = ClassLookup::new(
+            Manifest {
+                packages: HashSet::new(),
+                structs: HashMap::new(),
+            },
+            PackageFilter::default(),
+        );
+
+        let tokens = def.to_tokens(&owner, &lookup);
+        let actual = PrettyPlease::default().format_tokens(quote!(impl APyCharBase { #tokens })).unwrap();
+        let expected = PrettyPlease::default()
+            .format_tokens(quote! {
+                impl APyCharBase {
+                    #[doc = "`Jump`, flags `Final|Native|Public`, exec function at offset 0x1234"]
+                    pub fn try_jump(&self) -> Result<(), crate::SdkError> {
+                        static FUNCTION: crate::FunctionCache = crate::FunctionCache::new();
+
+                        let this_class = self.class.as_ref().ok_or(crate::SdkError::ClassNotFound("PyCharBase"))?;
+
+                        let func = FUNCTION
+                            .find(this_class, "Jump")
+                            .ok_or(crate::SdkError::FunctionNotFound {
+                                class: "PyCharBase",
+                                function: "Jump",
+                            })?;
+
+                        <params::APyCharBase_Jump as crate::FunctionParams>::check(func)?;
+
+                        let mut parms = params::APyCharBase_Jump {
+                            _marker: std::default::Default::default(),
+                        };
+
+                        let flags = func.function_flags;
+                        func.function_flags |= EFunctionFlags::Native;
+                        self.process_event(func, &mut parms);
+                        func.function_flags = flags;
+
+                        Ok(())
+                    }
+
+                    #[doc = "`Jump`, flags `Final|Native|Public`, exec function at offset 0x1234"]
+                    ///
+                    /// # Safety
+                    /// The exec thunk runs on a synthesized frame, see `UObject::call_native`.
+                    #[cfg(feature = "native_calls")]
+                    pub unsafe fn try_jump_native(&self) -> Result<(), crate::SdkError> {
+                        static FUNCTION: crate::FunctionCache = crate::FunctionCache::new();
+
+                        let this_class = self.class.as_ref().ok_or(crate::SdkError::ClassNotFound("PyCharBase"))?;
+
+                        let func = FUNCTION
+                            .find(this_class, "Jump")
+                            .ok_or(crate::SdkError::FunctionNotFound {
+                                class: "PyCharBase",
+                                function: "Jump",
+                            })?;
+
+                        <params::APyCharBase_Jump as crate::FunctionParams>::check(func)?;
+
+                        let mut parms = params::APyCharBase_Jump {
+                            _marker: std::default::Default::default(),
+                        };
+
+                        self.call_native(func, 4660usize, &mut parms)?;
+
+                        Ok(())
+                    }
+
+                    #[doc = "`Jump`, flags `Final|Native|Public`, exec function at offset 0x1234"]
+                    pub fn jump(&self) {
+                        self.try_jump().unwrap_or_else(|err| panic!("{}", err))
+                    }
+                }
+            })
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_function_params() {
+        let owner = StructDefinition {
+            struct_size: 40,
+            package: None,
+            name: "APyCharBase".into(),
+            parents: vec!["UObject".into()],
+            fields: vec![],
+            functions: vec![],
+            has_layout: true,
+        };
+
+        let def = FunctionDefinition {
+            name: "GetHeroID".into(),
+            return_value: TypeSignature::new_simple("i32".into(), FieldKind::Primitive),
+            arguments: vec![
+                ArgumentDefinition {
+                    name: "Slot".into(),
+                    is_out_param: false,
+                    type_: TypeSignature::new_simple("u8".into(), FieldKind::Primitive),
+                },
+                ArgumentDefinition {
+                    name: "OutName".into(),
+                    is_out_param: true,
+                    type_: TypeSignature::new_simple("FString".into(), FieldKind::Primitive),
+                },
+            ],
+            flags: "Final|Native|Public".into(),
+            offset: 0,
+        };
+
+        let lookup = ClassLookup::new(
+            Manifest {
+                packages: HashSet::new(),
+                structs: HashMap::new(),
+            },
+            PackageFilter::default(),
+        );
+
+        let actual = PrettyPlease::default()
+            .format_tokens(def.params_to_tokens(&owner, &lookup))
+            .unwrap();
+        let expected = PrettyPlease::default()
+            .format_tokens(quote! {
+                #[doc = "Parameters of `APyCharBase::GetHeroID`"]
+                #[repr(C)]
+                #[derive(Debug)]
+                pub struct APyCharBase_GetHeroID<'a> {
+                    #[doc = "`Slot`"]
+                    pub slot: u8,
+                    #[doc = "`OutName`"]
+                    pub out_name: FString,
+                    pub return_value: i32,
+                    pub _marker: std::marker::PhantomData<&'a u8>,
+                }
+
+                impl<'a> crate::FunctionParams for APyCharBase_GetHeroID<'a> {
+                    const CLASS: &'static str = "PyCharBase";
+                    const FUNCTION: &'static str = "GetHeroID";
+                    const PARMS_SIZE: usize = ::std::mem::offset_of!(Self, _marker);
+                }
+            })
+            .unwrap();
+        assert_eq!(actual, expected);
+
+        assert_eq!(
+            def.doc_lines(),
+            vec![
+                "`GetHeroID`, flags `Final|Native|Public`".to_string(),
+                String::new(),
+                "Parameters: `Slot`, `OutName` (out)".to_string(),
+            ]
+        );
+
+        let actual = PrettyPlease::default()
+            .format_tokens(def.params_test(&owner))
+            .unwrap();
+        let expected = PrettyPlease::default()
+            .format_tokens(quote! {
+                #[test]
+                fn test_params_APyCharBase_GetHeroID() {
+                    let offsets = [
+                        offset_of!(params::APyCharBase_GetHeroID, slot),
+                        offset_of!(params::APyCharBase_GetHeroID, out_name),
+                        offset_of!(params::APyCharBase_GetHeroID, return_value)
+                    ];
+                    assert!(offsets.windows(2).all(|it| it[0] < it[1]));
+                    assert!(<params::APyCharBase_GetHeroID as crate::FunctionParams>::PARMS_SIZE > offsets[offsets.len() - 1]);
+                    assert!(<params::APyCharBase_GetHeroID as crate::FunctionParams>::PARMS_SIZE <= size_of::<params::APyCharBase_GetHeroID>());
+                }
+            })
+            .unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_committed_manifest() {
+        let manifest = load_manifest(concat!(env!("CARGO_MANIFEST_DIR"), "/../manasdk/dump"))
+            .unwrap()
+            .expect("manasdk/dump has no package manifest");
+        let modules = manifest.modules();
+        for package in ["core_u_object", "engine", "input_core", "slate_core"] {
+            assert!(modules.contains(package), "{} is missing from the manifest", package);
+        }
+    }
 }
 
 fn as_identifier(name: &str) -> Ident {
@@ -1,6 +1,15 @@
-pub use generation::generate_code;
+pub use diff::*;
+pub use generation::{generate_code, load_manifest, DropReason, DroppedField, GeneratedCode, Inclusion, InclusionReason};
 pub use model::*;
+pub use output::{write_code, write_features, write_stubs, FEATURES_MARKER};
+pub use python::generate_stubs;
+pub use serialization::{Offset, OffsetData};
+pub use usmap::{Mappings, LATEST_USMAP_VERSION};
 
+mod diff;
 mod model;
 mod serialization;
 mod generation;
+mod output;
+mod python;
+mod usmap;
@@ -0,0 +1,180 @@
+use anyhow::{bail, Context};
+use generator::{Exclusions, PackageFilter, Roots};
+use regex::Regex;
+use std::fs::File;
+use std::path::PathBuf;
+
+const USAGE: &str = "\
+Usage: generator --dump <DIR> --out <DIR> [OPTIONS]
+       generator --dump <DIR> --write-manifest <FILE>
+       generator --dump <DIR> --write-features <Cargo.toml>
+       generator --dump <DIR> --python <DIR>
+
+Options:
+  --dump <DIR>          Directory containing the Dumper-7 dump, or a .usmap mappings file (enums and Python stubs only)
+  --out <DIR>           Directory the formatted modules and lib.rs are written to
+  --include <REGEX>     Only generate packages matching this pattern (plus their dependencies)
+  --exclude <REGEX>     Skip packages matching this pattern (unless they are a dependency)
+  --exclusions <FILE>   File listing types that are provided by hand-written code
+  --roots <FILE>        File listing root types: only what they reference is generated in full, the rest as opaque blobs
+  --inclusions <FILE>   Write a report of why each generated type was included
+  --write-manifest <FILE>
+                        Write the package manifest of the dump as compact JSON (e.g. dump/Manifest.json)
+  --write-features <Cargo.toml>
+                        Declare a cargo feature for every package of the dump (e.g. manasdk/Cargo.toml)
+  --python <DIR>        Write Python type stubs (.pyi) of the `unreal_engine` module into this directory
+  --dropped-fields <FILE>
+                        Write a report of all dumped fields that are missing from the generated structs
+  --diagnostics <FILE>  Write a report of the dump entries that could not be read and were skipped
+  --deny-warnings       Fail after writing the output if any dump entry was skipped
+  -h, --help            Print this help";
+
+#[derive(Debug, Default)]
+struct Args {
+    dump: Option<PathBuf>,
+    out: Option<PathBuf>,
+    include: Option<Regex>,
+    exclude: Option<Regex>,
+    exclusions: Option<PathBuf>,
+    roots: Option<PathBuf>,
+    inclusions: Option<PathBuf>,
+    write_manifest: Option<PathBuf>,
+    write_features: Option<PathBuf>,
+    python: Option<PathBuf>,
+    dropped_fields: Option<PathBuf>,
+    diagnostics: Option<PathBuf>,
+    deny_warnings: bool,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> anyhow::Result<Option<Self>> {
+        let mut result = Self::default();
+
+        while let Some(arg) = args.next() {
+            if arg == "-h" || arg == "--help" {
+                return Ok(None);
+            }
+
+            let mut value = || args.next().with_context(|| format!("Missing value for {}", arg));
+            match arg.as_str() {
+                "--dump" => result.dump = Some(value()?.into()),
+                "--out" => result.out = Some(value()?.into()),
+                "--include" => result.include = Some(Regex::new(&value()?).context("Invalid include pattern")?),
+                "--exclude" => result.exclude = Some(Regex::new(&value()?).context("Invalid exclude pattern")?),
+                "--exclusions" => result.exclusions = Some(value()?.into()),
+                "--roots" => result.roots = Some(value()?.into()),
+                "--inclusions" => result.inclusions = Some(value()?.into()),
+                "--write-manifest" => result.write_manifest = Some(value()?.into()),
+                "--write-features" => result.write_features = Some(value()?.into()),
+                "--python" => result.python = Some(value()?.into()),
+                "--dropped-fields" => result.dropped_fields = Some(value()?.into()),
+                "--diagnostics" => result.diagnostics = Some(value()?.into()),
+                "--deny-warnings" => result.deny_warnings = true,
+                _ => bail!("Unknown argument: {}\n\n{}", arg, USAGE),
+            }
+        }
+
+        Ok(Some(result))
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let Some(args) = Args::parse(std::env::args().skip(1))? else {
+        println!("{}", USAGE);
+        return Ok(());
+    };
+
+    let dump = args.dump.with_context(|| format!("--dump is required\n\n{}", USAGE))?;
+
+    if args.write_manifest.is_some() || args.write_features.is_some() {
+        let manifest = generator::load_manifest(&dump)?
+            .context("The dump contains neither GObjects-Dump.txt nor Manifest.json")?;
+
+        if let Some(path) = &args.write_manifest {
+            let file = File::create(path)
+                .with_context(|| format!("Unable to create manifest: {}", path.display()))?;
+            manifest.to_json(file)?;
+
+            println!("Wrote manifest with {} packages to {}", manifest.packages.len(), path.display());
+        }
+
+        if let Some(path) = &args.write_features {
+            let modules = manifest.modules();
+            generator::write_features(path, &modules)?;
+
+            println!("Wrote {} package features to {}", modules.len(), path.display());
+        }
+
+        if args.out.is_none() && args.python.is_none() {
+            return Ok(());
+        }
+    }
+
+    if let Some(path) = &args.python {
+        let stubs = generator::generate_stubs(&dump, PackageFilter::new(args.include.clone(), args.exclude.clone()))
+            .context("Failed to generate Python stubs")?;
+        let stub_count = stubs.len();
+        generator::write_stubs(path, stubs)?;
+
+        println!("Wrote {} Python stubs to {}", stub_count, path.display());
+        if args.out.is_none() {
+            return Ok(());
+        }
+    }
+
+    let out = args.out.with_context(|| format!("--out is required\n\n{}", USAGE))?;
+
+    let exclusions: Exclusions = match &args.exclusions {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Unable to read exclusions: {}", path.display()))?
+            .parse()?,
+        None => Exclusions::default(),
+    };
+
+    let roots: Roots = match &args.roots {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Unable to read roots: {}", path.display()))?
+            .parse()?,
+        None => Roots::default(),
+    };
+
+    let code = generator::generate_code(
+        &dump,
+        &exclusions.types(),
+        &roots.types(),
+        PackageFilter::new(args.include, args.exclude),
+    )
+    .context("Failed to generate code")?;
+
+    if let Some(path) = &args.inclusions {
+        std::fs::write(path, code.inclusions_report())
+            .with_context(|| format!("Unable to write report: {}", path.display()))?;
+        println!("Wrote the reasons for {} types to {}", code.inclusions.len(), path.display());
+    }
+
+    if let Some(path) = &args.dropped_fields {
+        std::fs::write(path, code.dropped_fields_report())
+            .with_context(|| format!("Unable to write report: {}", path.display()))?;
+        println!("Wrote {} dropped fields to {}", code.dropped_fields.len(), path.display());
+    }
+
+    match &args.diagnostics {
+        Some(path) => {
+            std::fs::write(path, code.diagnostics_report())
+                .with_context(|| format!("Unable to write report: {}", path.display()))?;
+            println!("Wrote {} diagnostics to {}", code.diagnostics.len(), path.display());
+        }
+        None => code.diagnostics.iter().for_each(|it| eprintln!("warning: {}", it)),
+    }
+
+    let module_count = code.modules.len();
+    let diagnostic_count = code.diagnostics.len();
+    let written = generator::write_code(&out, code.modules, &exclusions.overridden_packages())?;
+
+    println!("Wrote {} modules to {} ({} unchanged)", written, out.display(), module_count - written);
+    if args.deny_warnings && diagnostic_count > 0 {
+        bail!("{} dump entries were skipped, see the diagnostics", diagnostic_count);
+    }
+
+    Ok(())
+}
@@ -1,8 +1,9 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use heck::ToSnakeCase;
 use regex::Regex;
+use crate::OffsetData;
 
 #[derive(Clone, Debug)]
 pub struct Manifest {
@@ -38,6 +39,211 @@ impl FromStr for Manifest {
     }
 }
 
+impl Manifest {
+    /// Builds a best-effort manifest from the dumps alone.
+    ///
+    /// The JSON dumps carry no package information, so only blueprint generated classes (`*_C`)
+    /// can be assigned to a package, see [Manifest::infer_blueprints].
+    /// Everything else ends up without a package.
+    pub fn infer(dumps: &[&StructDump]) -> Self {
+        let mut result = Self {
+            structs: HashMap::new(),
+            packages: HashSet::new(),
+        };
+
+        result.infer_blueprints(dumps);
+        result
+    }
+
+    /// Assigns the blueprint generated classes (`*_C`) the manifest doesn't know to a package
+    /// named after their blueprint asset.
+    ///
+    /// This keeps a checked in `Manifest.json` down to the script packages.
+    pub fn infer_blueprints(&mut self, dumps: &[&StructDump]) {
+        for item in dumps.iter().flat_map(|it| it.data.iter()) {
+            let Some(name) = item.name.get(1..) else {
+                continue;
+            };
+
+            if self.structs.contains_key(name) {
+                continue;
+            }
+
+            if let Some(package) = name.strip_suffix("_C").filter(|it| !it.is_empty()) {
+                self.packages.insert(package.to_string());
+                self.structs.insert(name.to_string(), package.to_string());
+            }
+        }
+    }
+
+    /// Names of the modules the packages of this manifest end up in, i.e. the snake cased names
+    /// of all packages that contain at least one type.
+    pub fn modules(&self) -> BTreeSet<String> {
+        self.structs.values().map(|it| it.to_snake_case()).collect()
+    }
+}
+
+/// Types that are not generated because they are provided by hand-written code in `manasdk`.
+///
+/// Each line holds a type name, optionally followed by the override module that provides it
+/// (e.g. `UObject core_u_object`). Everything after a `#` is ignored.
+#[derive(Clone, Debug, Default)]
+pub struct Exclusions {
+    pub entries: Vec<(String, Option<String>)>,
+}
+
+impl FromStr for Exclusions {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut result = Self::default();
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let mut parts = line.split_whitespace();
+
+            let Some(name) = parts.next() else {
+                continue;
+            };
+
+            let package = parts.next().map(|it| it.to_string());
+            if parts.next().is_some() {
+                anyhow::bail!("Invalid exclusion on line {}: {}", index + 1, line.trim());
+            }
+
+            result.entries.push((name.to_string(), package));
+        }
+
+        Ok(result)
+    }
+}
+
+impl Exclusions {
+    pub fn types(&self) -> Vec<&str> {
+        self.entries.iter().map(|it| it.0.as_str()).collect()
+    }
+
+    /// Packages whose generated module re-exports a hand-written override module.
+    pub fn overridden_packages(&self) -> HashSet<&str> {
+        self.entries.iter().filter_map(|it| it.1.as_deref()).collect()
+    }
+}
+
+/// Types a pruned generation starts from, see [crate::generate_code].
+///
+/// Each line holds a type name. Everything after a `#` is ignored.
+#[derive(Clone, Debug, Default)]
+pub struct Roots {
+    pub entries: Vec<String>,
+}
+
+impl FromStr for Roots {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut result = Self::default();
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let mut parts = line.split_whitespace();
+
+            let Some(name) = parts.next() else {
+                continue;
+            };
+
+            if parts.next().is_some() {
+                anyhow::bail!("Invalid root on line {}: {}", index + 1, line.trim());
+            }
+
+            result.entries.push(name.to_string());
+        }
+
+        Ok(result)
+    }
+}
+
+impl Roots {
+    pub fn types(&self) -> Vec<&str> {
+        self.entries.iter().map(|it| it.as_str()).collect()
+    }
+}
+
+/// Decides which packages are taken as a starting point for the generation.
+///
+/// Types without a package always match.
+#[derive(Clone, Debug, Default)]
+pub struct PackageFilter {
+    pub include: Option<Regex>,
+    pub exclude: Option<Regex>,
+}
+
+impl PackageFilter {
+    pub fn new(include: Option<Regex>, exclude: Option<Regex>) -> Self {
+        Self { include, exclude }
+    }
+
+    pub fn is_match(&self, package: Option<&str>) -> bool {
+        let Some(package) = package else {
+            return true;
+        };
+
+        let included = self.include.as_ref().is_none_or(|it| it.is_match(package));
+        let excluded = self.exclude.as_ref().is_some_and(|it| it.is_match(package));
+
+        included && !excluded
+    }
+}
+
+/// All information contained in a Dumper-7 dump.
+#[derive(Clone, Debug)]
+pub struct Dump {
+    pub classes: StructDump,
+    pub structs: StructDump,
+    pub enums: EnumDump,
+    pub functions: FunctionDump,
+    pub offsets: OffsetData,
+    pub format: DumpFormat,
+    /// Entries that could not be read and were skipped.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// The flavor of Dumper-7 a dump was written by, as detected when loading it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DumpFormat {
+    /// The `version` of the Dumpspace JSON files.
+    pub version: u32,
+    /// Whether out params are marked, which needs the modified Dumper-7. Stock dumps pass everything as inputs.
+    pub marks_out_params: bool,
+}
+
+/// An entry of the dump that could not be used as-is, so it was skipped instead of aborting the generation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub type_name: String,
+    /// The field or function of the type, if the problem is limited to it.
+    pub member: Option<String>,
+    pub reason: String,
+}
+
+impl Diagnostic {
+    pub fn new(type_name: &str, member: Option<&str>, reason: impl Into<String>) -> Self {
+        Self {
+            type_name: type_name.to_string(),
+            member: member.map(|it| it.to_string()),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.member {
+            Some(member) => write!(f, "{}::{}: {}", self.type_name, member, self.reason),
+            None => write!(f, "{}: {}", self.type_name, self.reason),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct EnumDump {
     pub data: Vec<EnumDefinition>,
@@ -97,19 +303,30 @@ pub struct ClassLookup {
     classes: HashMap<String, StructDefinition>,
     enums: HashMap<String, EnumDefinition>,
     manifest: Manifest,
-    filter: Option<Regex>,
+    filter: PackageFilter,
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl ClassLookup {
-    pub fn new(manifest: Manifest, filter: Option<Regex>) -> Self {
+    pub fn new(manifest: Manifest, filter: PackageFilter) -> Self {
         Self {
             manifest,
             filter,
             classes: HashMap::new(),
             enums: HashMap::new(),
+            diagnostics: vec![],
         }
     }
 
+    /// Problems with the dump, both from reading it and from adding it to the lookup.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn add_diagnostics(&mut self, diagnostics: impl IntoIterator<Item = Diagnostic>) {
+        self.diagnostics.extend(diagnostics);
+    }
+
     pub fn add_struct_dump(&mut self, dump: StructDump) {
         self.classes.reserve(dump.data.len());
         for mut item in dump.data {
@@ -119,8 +336,38 @@ impl ClassLookup {
 
             item.package = package;
 
-            if let Some(old_value) = self.classes.insert(item.name.clone(), item) {
-                panic!("Value clash {:?}", old_value);
+            // Types are unique per name in Unreal, so a second definition means the dump itself is off
+            if self.classes.contains_key(&item.name) {
+                self.diagnostics.push(Diagnostic::new(&item.name, None, "defined more than once, keeping the first definition"));
+            } else {
+                self.classes.insert(item.name.clone(), item);
+            }
+        }
+    }
+
+    /// Resolves parents that only exist under a package qualified name.
+    ///
+    /// The dump qualifies both types when two packages define the same name (e.g. `AIModule::UBTTask_MoveTo`),
+    /// but still refers to them by the bare name as a parent. Among the qualified candidates, the parent is the
+    /// one whose own ancestors continue those of the child.
+    pub fn resolve_qualified_parents(&mut self) {
+        let resolved: Vec<_> = self.classes.values()
+            .filter_map(|class| {
+                let parent = class.parents.first().filter(|it| !self.classes.contains_key(*it))?;
+                let suffix = format!("__{}", parent);
+                let mut candidates = self.classes.values()
+                    .filter(|it| it.name.ends_with(&suffix) && it.parents[..] == class.parents[1..]);
+
+                match (candidates.next(), candidates.next()) {
+                    (Some(candidate), None) => Some((class.name.clone(), candidate.name.clone())),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        for (name, parent) in resolved {
+            if let Some(class) = self.classes.get_mut(&name) {
+                class.parents[0] = parent;
             }
         }
     }
@@ -142,8 +389,10 @@ impl ClassLookup {
 
             item.package = package;
 
-            if let Some(old_value) = self.enums.insert(item.name.clone(), item) {
-                panic!("Value clash {:?}", old_value);
+            if self.enums.contains_key(&item.name) {
+                self.diagnostics.push(Diagnostic::new(&item.name, None, "defined more than once, keeping the first definition"));
+            } else {
+                self.enums.insert(item.name.clone(), item);
             }
         }
     }
@@ -156,23 +405,29 @@ impl ClassLookup {
         self.enums.get(name)
     }
 
-    pub fn iter_structs(&self) -> impl Iterator<Item=&StructDefinition> {
-        self.classes.values().filter(|&class| {
-            let package = class.package.as_ref();
+    /// Strips the fields and functions of every struct that `keep` rejects,
+    /// leaving opaque blobs that only extend their parent up to the struct size.
+    pub fn make_opaque(&mut self, keep: impl Fn(&str) -> bool) {
+        for class in self.classes.values_mut().filter(|it| !keep(&it.name)) {
+            class.fields.clear();
+            class.functions.clear();
+        }
+    }
 
-            if let Some(filter) = &self.filter {
-                package.is_none() || filter.is_match(package.unwrap().as_str())
-            } else { true }
-        })
+    /// Removes the structs whose layout is unknown, returning how many there were.
+    pub fn remove_structs_without_layout(&mut self) -> usize {
+        let count = self.classes.len();
+        self.classes.retain(|_, it| it.has_layout);
+
+        count - self.classes.len()
+    }
+
+    pub fn iter_structs(&self) -> impl Iterator<Item=&StructDefinition> {
+        self.classes.values().filter(|&class| self.filter.is_match(class.package.as_deref()))
     }
 
     pub fn iter_enums(&self) -> impl Iterator<Item=&EnumDefinition> {
-        self.enums.values().filter(|&class| {
-            let package = class.package.as_ref();
-            if let Some(filter) = &self.filter {
-                package.is_none() || filter.is_match(package.unwrap().as_str())
-            } else { true }
-        })
+        self.enums.values().filter(|&class| self.filter.is_match(class.package.as_deref()))
     }
 }
 
@@ -190,6 +445,9 @@ pub struct StructDefinition {
     pub fields: Vec<FieldDefinition>,
     pub package: Option<String>,
     pub functions: Vec<FunctionDefinition>,
+    /// Whether the struct size and the offsets and sizes of the fields are known.
+    /// `.usmap` mappings only carry the names and types of the properties.
+    pub has_layout: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -198,6 +456,8 @@ pub struct FieldDefinition {
     pub offset: usize,
     pub size: usize,
     pub bit_offset: Option<u8>,
+    /// Number of bits of a bitfield. Always 1 for the bool bitfields reflected by Unreal.
+    pub bit_width: u8,
     pub unknown: usize,
     pub signature: TypeSignature,
 }
@@ -220,7 +480,7 @@ pub struct ArgumentDefinition {
 
 impl FieldDefinition {
     pub fn new(name: String, offset: usize, size: usize, unknown: usize, bit_offset: Option<u8>, signature: TypeSignature) -> Self {
-        Self { name, offset, size, bit_offset, unknown, signature }
+        Self { name, offset, size, bit_offset, bit_width: 1, unknown, signature }
     }
 }
 
@@ -285,10 +545,142 @@ impl TypeSignature {
     }
 }
 
+impl Display for TypeSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        if !self.generics.is_empty() {
+            write!(f, "<{}>", self.generics.iter().map(|it| it.to_string()).collect::<Vec<_>>().join(", "))?;
+        }
+
+        if self.is_pointer {
+            write!(f, "*")?;
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum FieldKind {
     Struct,
     Class,
     Primitive,
     Enum,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exclusions() {
+        let exclusions: Exclusions = "# Comment\nUObject core_u_object\n\nFName # Inline comment\n".parse().unwrap();
+
+        assert_eq!(exclusions.types(), vec!["UObject", "FName"]);
+        assert_eq!(exclusions.overridden_packages(), HashSet::from(["core_u_object"]));
+        assert!("UObject core_u_object extra".parse::<Exclusions>().is_err());
+    }
+
+    #[test]
+    fn test_roots() {
+        let roots: Roots = "# Comment\nAPyCharBase\n\nUGameplayStatics # Inline comment\n".parse().unwrap();
+
+        assert_eq!(roots.types(), vec!["APyCharBase", "UGameplayStatics"]);
+        assert!("APyCharBase engine".parse::<Roots>().is_err());
+    }
+
+    #[test]
+    fn test_package_filter() {
+        let filter = PackageFilter::new(Some(Regex::new("engine|x21").unwrap()), Some(Regex::new("^x21_").unwrap()));
+
+        assert!(filter.is_match(None));
+        assert!(filter.is_match(Some("engine")));
+        assert!(filter.is_match(Some("x21")));
+        assert!(!filter.is_match(Some("x21_game_mode")));
+        assert!(!filter.is_match(Some("ai_module")));
+    }
+
+    #[test]
+    fn test_infer_blueprints() {
+        let def = |name: &str| StructDefinition {
+            name: name.into(),
+            parents: vec![],
+            struct_size: 0,
+            fields: vec![],
+            package: None,
+            functions: vec![],
+            has_layout: true,
+        };
+        let mut manifest = Manifest {
+            packages: HashSet::from(["X21".to_string()]),
+            structs: HashMap::from([("ActorBase".to_string(), "X21".to_string())]),
+        };
+        manifest.infer_blueprints(&[&StructDump { data: vec![def("AActorBase"), def("UWBP_HUD_C"), def("AEmitter")] }]);
+
+        assert_eq!(manifest.structs.get("ActorBase").map(String::as_str), Some("X21"));
+        assert_eq!(manifest.structs.get("WBP_HUD_C").map(String::as_str), Some("WBP_HUD"));
+        assert_eq!(manifest.structs.get("Emitter"), None);
+        assert_eq!(manifest.modules(), BTreeSet::from(["wbp_hud".to_string(), "x21".to_string()]));
+    }
+
+    #[test]
+    fn test_resolve_qualified_parents() {
+        let def = |name: &str, parents: &[&str]| StructDefinition {
+            name: name.into(),
+            parents: parents.iter().map(|it| it.to_string()).collect(),
+            struct_size: 0,
+            fields: vec![],
+            package: None,
+            functions: vec![],
+            has_layout: true,
+        };
+        let mut lookup = ClassLookup::new(
+            Manifest {
+                packages: HashSet::new(),
+                structs: HashMap::new(),
+            },
+            PackageFilter::default(),
+        );
+        lookup.add_struct_dump(StructDump {
+            data: vec![
+                def("AIModule__UBTTask_MoveTo", &["UBTTask_BlackboardBase", "UObject"]),
+                def("bt_task_move_to__UBTTask_MoveTo", &["UBTTask_BlueprintBase", "UObject"]),
+                def("UBTTask_MoveDirectlyToward", &["UBTTask_MoveTo", "UBTTask_BlackboardBase", "UObject"]),
+            ],
+        });
+        lookup.resolve_qualified_parents();
+
+        assert_eq!(
+            lookup.get_struct("UBTTask_MoveDirectlyToward").map(|it| it.parents[0].as_str()),
+            Some("AIModule__UBTTask_MoveTo")
+        );
+    }
+
+    #[test]
+    fn test_value_clash() {
+        let def = |size| StructDefinition {
+            name: "FMyStruct".into(),
+            parents: vec![],
+            struct_size: size,
+            fields: vec![],
+            package: None,
+            functions: vec![],
+            has_layout: true,
+        };
+        let mut lookup = ClassLookup::new(
+            Manifest {
+                packages: HashSet::new(),
+                structs: HashMap::new(),
+            },
+            PackageFilter::default(),
+        );
+        lookup.add_struct_dump(StructDump { data: vec![def(0x10), def(0x20)] });
+
+        assert_eq!(lookup.get_struct("FMyStruct").map(|it| it.struct_size), Some(0x10));
+        assert_eq!(
+            lookup.diagnostics(),
+            &[Diagnostic::new("FMyStruct", None, "defined more than once, keeping the first definition")]
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use anyhow::Context;
+use rust_format::{Formatter, PrettyPlease};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs::File;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// File in the output directory recording the hash of the unformatted code of every module written by [write_code].
+pub const HASHES_FILE: &str = "hashes.txt";
+
+/// Formats the generated definitions and writes them into `output_path`.
+///
+/// Every package ends up in its own `<package>.rs` file, while types without a package go
+/// into `lib.rs` together with the module declarations of all packages.
+/// Packages listed in `overridden_packages` re-export their hand-written counterpart from `crate::overrides`,
+/// and are written even if none of their types were generated.
+///
+/// Modules whose code is unchanged since the last call are neither formatted nor rewritten, so their files keep
+/// their timestamps for cargo and rustc. Files of modules that are no longer generated are removed.
+/// Returns the number of modules that were written.
+pub fn write_code<P: AsRef<Path>>(
+    output_path: P,
+    mut definitions: HashMap<Option<String>, String>,
+    overridden_packages: &HashSet<&str>,
+) -> anyhow::Result<usize> {
+    let output_path = output_path.as_ref();
+    std::fs::create_dir_all(output_path).context("Unable to create output directory")?;
+
+    // Removed until all modules are written, so that an interrupted run cannot leave a stale hash behind
+    let hashes_path = output_path.join(HASHES_FILE);
+    let previous_hashes = read_hashes(&hashes_path);
+    if hashes_path.exists() {
+        std::fs::remove_file(&hashes_path).context("Unable to remove hashes")?;
+    }
+
+    for package in overridden_packages {
+        definitions.entry(Some(package.to_string())).or_default();
+    }
+
+    let mut modules: Vec<_> = definitions
+        .keys()
+        .filter_map(|package| package.clone())
+        .collect();
+    modules.sort();
+
+    let mut hashes = BTreeMap::new();
+    let mut written = 0;
+    for (package, mut def) in definitions {
+        match package.as_deref() {
+            None => {
+                let mut imports = String::new();
+                for module in modules.iter() {
+                    imports.push_str(format!("pub mod {};", module).as_str());
+                }
+
+                def.insert_str(0, imports.as_str());
+            }
+            Some(package) if overridden_packages.contains(package) => {
+                def.insert_str(0, format!("pub use crate::overrides::{}::*;", package).as_str());
+            }
+            Some(_) => {}
+        }
+
+        let module = package.unwrap_or("lib".to_string());
+        let path = output_path.join(format!("{}.rs", module));
+
+        let mut hasher = DefaultHasher::new();
+        def.hash(&mut hasher);
+        let hash = hasher.finish();
+        let unchanged = previous_hashes.get(&module) == Some(&hash) && path.exists();
+        hashes.insert(module.clone(), hash);
+        if unchanged {
+            continue;
+        }
+
+        let result = PrettyPlease::default()
+            .format_str(def)
+            .with_context(|| format!("Failed to format code: {}", module))?;
+        let mut file = File::create(&path)
+            .with_context(|| format!("Failed to create output file: {}", path.display()))?;
+        write!(file, "{}", result)?;
+        written += 1;
+    }
+
+    for module in previous_hashes.keys().filter(|it| !hashes.contains_key(*it)) {
+        let path = output_path.join(format!("{}.rs", module));
+        if path.exists() {
+            std::fs::remove_file(&path).with_context(|| format!("Failed to remove output file: {}", path.display()))?;
+        }
+    }
+
+    let content: String = hashes.iter().map(|(module, hash)| format!("{} {:016x}\n", module, hash)).collect();
+    std::fs::write(&hashes_path, content).context("Unable to write hashes")?;
+
+    Ok(written)
+}
+
+/// Reads the hashes of the last [write_code], treating a missing or malformed file as empty.
+fn read_hashes(path: &Path) -> HashMap<String, u64> {
+    let content = std::fs::read_to_string(path).unwrap_or_default();
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let (module, hash) = line.split_once(' ')?;
+            Some((module.to_string(), u64::from_str_radix(hash, 16).ok()?))
+        })
+        .collect()
+}
+
+/// Writes stubs as returned by [crate::generate_stubs] into `output_path`.
+pub fn write_stubs<P: AsRef<Path>>(output_path: P, stubs: HashMap<PathBuf, String>) -> anyhow::Result<()> {
+    let output_path = output_path.as_ref();
+
+    for (relative_path, content) in stubs {
+        let path = output_path.join(relative_path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context("Unable to create output directory")?;
+        }
+
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write stub: {}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Marks the start of the package features in the `[features]` table of a `Cargo.toml`.
+/// Everything after it up to the next table is replaced by [write_features].
+pub const FEATURES_MARKER: &str = "# Package features";
+
+/// Declares one cargo feature per module in the `Cargo.toml` at `cargo_toml_path`.
+///
+/// Hand-written features have to go before the [FEATURES_MARKER] line.
+pub fn write_features<P: AsRef<Path>>(cargo_toml_path: P, modules: &BTreeSet<String>) -> anyhow::Result<()> {
+    let path = cargo_toml_path.as_ref();
+    let manifest = std::fs::read_to_string(path)
+        .with_context(|| format!("Unable to read {}", path.display()))?;
+    let updated = replace_features(&manifest, modules)
+        .with_context(|| format!("{} contains no \"{}\" line", path.display(), FEATURES_MARKER))?;
+
+    std::fs::write(path, updated).with_context(|| format!("Unable to write {}", path.display()))
+}
+
+fn replace_features(manifest: &str, modules: &BTreeSet<String>) -> Option<String> {
+    if !manifest.lines().any(|it| it.starts_with(FEATURES_MARKER)) {
+        return None;
+    }
+
+    let mut lines = manifest.lines();
+    let mut result = String::new();
+
+    for line in lines.by_ref() {
+        result.push_str(line);
+        result.push('\n');
+
+        if line.starts_with(FEATURES_MARKER) {
+            break;
+        }
+    }
+
+    for module in modules.iter().filter(|it| !["default", "full", "native_calls", "pruned"].contains(&it.as_str())) {
+        result.push_str(format!("{} = []\n", module).as_str());
+    }
+
+    // Keep whatever follows the features table
+    let mut rest = lines.skip_while(|it| !it.starts_with('['));
+    if let Some(table) = rest.next() {
+        result.push('\n');
+        result.push_str(table);
+        result.push('\n');
+        for line in rest {
+            result.push_str(line);
+            result.push('\n');
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_features() {
+        let manifest = "[package]\nname = \"sdk\"\n\n[features]\nfull = []\n# Package features\nold = []\n\n[dependencies]\nregex = \"1\"\n";
+        let modules = BTreeSet::from(["engine".to_string(), "core_u_object".to_string()]);
+
+        assert_eq!(
+            replace_features(manifest, &modules).unwrap(),
+            "[package]\nname = \"sdk\"\n\n[features]\nfull = []\n# Package features\ncore_u_object = []\nengine = []\n\n[dependencies]\nregex = \"1\"\n"
+        );
+        assert_eq!(replace_features("[features]\n", &modules), None);
+    }
+
+    #[test]
+    fn test_incremental_write() {
+        let output_path = std::env::temp_dir().join(format!("generator-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&output_path);
+        let definitions = |engine: &str, x21: Option<&str>| {
+            let mut result = HashMap::from([(None, String::new()), (Some("engine".to_string()), engine.to_string())]);
+            if let Some(x21) = x21 {
+                result.insert(Some("x21".to_string()), x21.to_string());
+            }
+            result
+        };
+        let write = |definitions| write_code(&output_path, definitions, &HashSet::new()).unwrap();
+
+        assert_eq!(write(definitions("pub struct A;", Some("pub struct B;"))), 3);
+        assert_eq!(write(definitions("pub struct A;", Some("pub struct B;"))), 0);
+        assert_eq!(write(definitions("pub struct A2;", Some("pub struct B;"))), 1);
+        assert_eq!(std::fs::read_to_string(output_path.join("engine.rs")).unwrap(), "pub struct A2;\n");
+
+        // Dropping a package changes the module declarations of lib.rs and removes its file
+        assert_eq!(write(definitions("pub struct A2;", None)), 1);
+        assert!(!output_path.join("x21.rs").exists());
+        assert_eq!(std::fs::read_to_string(output_path.join("lib.rs")).unwrap(), "pub mod engine;\n");
+
+        // Files that went missing are written again
+        std::fs::remove_file(output_path.join("engine.rs")).unwrap();
+        assert_eq!(write(definitions("pub struct A2;", None)), 1);
+
+        // Overridden packages are written without any generated types
+        write_code(&output_path, definitions("pub struct A2;", None), &HashSet::from(["input_core"])).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(output_path.join("input_core.rs")).unwrap(),
+            "pub use crate::overrides::input_core::*;\n"
+        );
+
+        std::fs::remove_dir_all(&output_path).unwrap();
+    }
+}
@@ -0,0 +1,518 @@
+use crate::generation::load_lookup;
+use crate::{ClassLookup, EnumDefinition, FieldKind, FunctionDefinition, PackageFilter, StructDefinition, TypeSignature};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const HEADER: &str = "# Generated from the Dumper-7 dump, do not edit.\n";
+
+/// Module for types without a package.
+const UNASSIGNED_MODULE: &str = "_unassigned";
+
+/// Hand-written part of the `unreal_engine` module that does not come from the dump.
+const CORE_STUB: &str = r#"from typing import Any
+
+from unreal_engine import classes as classes, enums as enums, structs as structs
+
+def log(message: Any) -> None: ...
+def log_warning(message: Any) -> None: ...
+def log_error(message: Any) -> None: ...
+def is_valid(obj: Any) -> bool: ...
+
+class FVector:
+    x: float
+    y: float
+    z: float
+    def __init__(self, x: float = ..., y: float = ..., z: float = ...) -> None: ...
+
+class FVector2D:
+    x: float
+    y: float
+    def __init__(self, x: float = ..., y: float = ...) -> None: ...
+
+class FRotator:
+    roll: float
+    pitch: float
+    yaw: float
+    def __init__(self, roll: float = ..., pitch: float = ..., yaw: float = ...) -> None: ...
+
+class FTransform:
+    translation: FVector
+    rotation: FRotator
+    scale: FVector
+    def __init__(self, translation: FVector = ..., rotation: FRotator = ..., scale: FVector = ...) -> None: ...
+"#;
+
+/// Methods that UnrealEnginePython adds to every object.
+fn object_methods(class_type: &str, world_type: &str) -> String {
+    format!(
+        r#"    def get_name(self) -> str: ...
+    def get_full_name(self) -> str: ...
+    def get_class(self) -> {class_type}: ...
+    def is_a(self, cls: Any) -> bool: ...
+    def is_valid(self) -> bool: ...
+    def call(self, command: str) -> None: ...
+    def call_function(self, name: str, *args: Any) -> Any: ...
+    def get_property(self, name: str) -> Any: ...
+    def set_property(self, name: str, value: Any) -> None: ...
+    def get_world(self) -> {world_type}: ...
+"#
+    )
+}
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in",
+    "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+/// Generates Python type stubs of the `unreal_engine` module exposed by UnrealEnginePython.
+///
+/// Every package ends up in `unreal_engine/packages/<package>.pyi`, which are re-exported through
+/// `unreal_engine.classes`, `unreal_engine.structs` and `unreal_engine.enums` like at runtime.
+/// Classes and structs go by their name without prefix, enums keep theirs.
+/// Returns the content of every stub by its path relative to the output directory.
+pub fn generate_stubs<P: AsRef<Path>>(
+    base_path: P,
+    package_filter: PackageFilter,
+) -> anyhow::Result<HashMap<PathBuf, String>> {
+    let (lookup, _) = load_lookup(base_path, package_filter)?;
+    Ok(StubGenerator::new(&lookup).generate())
+}
+
+struct StubGenerator<'a> {
+    lookup: &'a ClassLookup,
+    structs: Vec<&'a StructDefinition>,
+    enums: Vec<&'a EnumDefinition>,
+    /// Names of all types that get a stub
+    emitted: HashSet<&'a str>,
+}
+
+/// Per-module state while writing a stub.
+struct StubModule<'a> {
+    name: String,
+    imports: BTreeMap<String, BTreeSet<String>>,
+    body: String,
+    /// Python names already declared in the module
+    declared: HashSet<String>,
+    generator: &'a StubGenerator<'a>,
+}
+
+impl<'a> StubGenerator<'a> {
+    fn new(lookup: &'a ClassLookup) -> Self {
+        let mut structs: Vec<_> = lookup.iter_structs().collect();
+        structs.sort_by(|a, b| a.name.cmp(&b.name));
+        // Classes first, so they win if a struct of the same package has the same name
+        structs.sort_by_key(|it| is_struct(it));
+
+        let mut enums: Vec<_> = lookup.iter_enums().collect();
+        enums.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let emitted = structs
+            .iter()
+            .map(|it| it.name.as_str())
+            .chain(enums.iter().map(|it| it.name.as_str()))
+            .collect();
+
+        Self { lookup, structs, enums, emitted }
+    }
+
+    fn generate(&self) -> HashMap<PathBuf, String> {
+        let mut modules: BTreeMap<String, StubModule> = BTreeMap::new();
+        let mut namespaces: BTreeMap<&str, BTreeMap<String, BTreeSet<String>>> = BTreeMap::new();
+
+        for def in self.structs.iter() {
+            let module_name = module_name(def.package.as_deref());
+            let module = modules
+                .entry(module_name.clone())
+                .or_insert_with(|| StubModule::new(module_name.clone(), self));
+
+            if module.write_struct(def) {
+                let namespace = if is_struct(def) { "structs" } else { "classes" };
+                namespaces
+                    .entry(namespace)
+                    .or_default()
+                    .entry(module_name)
+                    .or_default()
+                    .insert(python_name(&def.name));
+            }
+        }
+
+        for def in self.enums.iter() {
+            let module_name = module_name(def.package.as_deref());
+            let module = modules
+                .entry(module_name.clone())
+                .or_insert_with(|| StubModule::new(module_name.clone(), self));
+
+            if module.write_enum(def) {
+                namespaces
+                    .entry("enums")
+                    .or_default()
+                    .entry(module_name)
+                    .or_default()
+                    .insert(def.name.clone());
+            }
+        }
+
+        let mut result = HashMap::new();
+        result.insert(PathBuf::from("unreal_engine/__init__.pyi"), format!("{}{}", HEADER, CORE_STUB));
+        result.insert(PathBuf::from("unreal_engine/packages/__init__.pyi"), HEADER.to_string());
+
+        for namespace in ["classes", "structs", "enums"] {
+            let mut content = HEADER.to_string();
+            for (module, names) in namespaces.get(namespace).into_iter().flatten() {
+                let names = names.iter().map(|it| format!("{} as {}", it, it)).collect::<Vec<_>>();
+                writeln!(content, "from unreal_engine.packages.{} import {}", module, names.join(", ")).unwrap();
+            }
+
+            result.insert(PathBuf::from(format!("unreal_engine/{}.pyi", namespace)), content);
+        }
+
+        for (name, module) in modules {
+            result.insert(PathBuf::from(format!("unreal_engine/packages/{}.pyi", name)), module.finish());
+        }
+
+        result
+    }
+
+    /// Resolves a dumped type to a Python name, `None` if it has no stub.
+    fn resolve(&self, name: &str) -> Option<(String, String)> {
+        if !self.emitted.contains(name) {
+            return None;
+        }
+
+        if let Some(def) = self.lookup.get_struct(name) {
+            Some((module_name(def.package.as_deref()), python_name(&def.name)))
+        } else {
+            self.lookup
+                .get_enum(name)
+                .map(|def| (module_name(def.package.as_deref()), def.name.clone()))
+        }
+    }
+}
+
+impl<'a> StubModule<'a> {
+    fn new(name: String, generator: &'a StubGenerator<'a>) -> Self {
+        Self {
+            name,
+            imports: BTreeMap::new(),
+            body: String::new(),
+            declared: HashSet::new(),
+            generator,
+        }
+    }
+
+    fn finish(self) -> String {
+        let mut result = HEADER.to_string();
+        result.push_str("from enum import IntEnum, IntFlag\nfrom typing import Any\n");
+        for (module, names) in self.imports.iter() {
+            let names = names.iter().cloned().collect::<Vec<_>>();
+            writeln!(result, "from unreal_engine.packages.{} import {}", module, names.join(", ")).unwrap();
+        }
+
+        result.push_str(&self.body);
+        result
+    }
+
+    /// Refers to a dumped type, importing it if it lives in another module.
+    fn type_reference(&mut self, name: &str) -> String {
+        match self.generator.resolve(name) {
+            Some((module, python_name)) => {
+                if module != self.name {
+                    self.imports.entry(module).or_default().insert(python_name.clone());
+                }
+                python_name
+            }
+            None => "Any".to_string(),
+        }
+    }
+
+    fn python_type(&mut self, signature: &TypeSignature) -> String {
+        match (signature.kind, signature.name.as_str()) {
+            (_, "bool") => "bool".to_string(),
+            (_, "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "usize") => "int".to_string(),
+            (_, "f32" | "f64") => "float".to_string(),
+            (_, "FString" | "FName" | "FText") => "str".to_string(),
+            (_, "void") => "None".to_string(),
+            (_, "TArray") => format!("list[{}]", self.generic_type(signature, 0)),
+            (_, "TSet") => format!("set[{}]", self.generic_type(signature, 0)),
+            (_, "TMap") => format!(
+                "dict[{}, {}]",
+                self.generic_type(signature, 0),
+                self.generic_type(signature, 1)
+            ),
+            (_, "TWeakObjectPtr" | "TSoftObjectPtr" | "TLazyObjectPtr" | "TScriptInterface") => {
+                self.generic_type(signature, 0)
+            }
+            (_, "TSubclassOf" | "TSoftClassPtr") => self.type_reference("UClass"),
+            // Flags are combined, so they are not necessarily a member of the enum
+            (FieldKind::Enum, name) if name.ends_with("Flags") => "int".to_string(),
+            (FieldKind::Primitive, _) => "Any".to_string(),
+            (_, name) => self.type_reference(name),
+        }
+    }
+
+    fn generic_type(&mut self, signature: &TypeSignature, index: usize) -> String {
+        match signature.generics.get(index) {
+            Some(generic) => self.python_type(generic),
+            None => "Any".to_string(),
+        }
+    }
+
+    /// Declares a Python name, `false` if it is already taken in this module.
+    fn declare(&mut self, name: String) -> bool {
+        self.declared.insert(name)
+    }
+
+    fn write_struct(&mut self, def: &StructDefinition) -> bool {
+        let name = python_name(&def.name);
+        if !self.declare(name.clone()) {
+            return false;
+        }
+
+        let parent = def
+            .parents
+            .first()
+            .map(|it| self.type_reference(it))
+            .filter(|it| it != "Any");
+
+        let mut body = String::new();
+        for field in def.fields.iter() {
+            let type_ = if field.bit_offset.is_some() {
+                "bool".to_string()
+            } else {
+                self.python_type(&field.signature)
+            };
+            writeln!(body, "    {}: {}", py_identifier(&field.name), type_).unwrap();
+        }
+
+        for function in def.functions.iter() {
+            let signature = self.function_signature(function);
+            body.push_str(&signature);
+        }
+
+        if def.name == "UObject" {
+            let class_type = self.type_reference("UClass");
+            let world_type = self.type_reference("UWorld");
+            body.push_str(&object_methods(&class_type, &world_type));
+        }
+
+        if body.is_empty() {
+            body.push_str("    ...\n");
+        }
+
+        match parent {
+            Some(parent) => writeln!(self.body, "\nclass {}({}):", name, parent).unwrap(),
+            None => writeln!(self.body, "\nclass {}:", name).unwrap(),
+        }
+        self.body.push_str(&body);
+
+        true
+    }
+
+    /// Out params are optional and returned as a tuple together with the return value,
+    /// the way UnrealEnginePython passes them.
+    fn function_signature(&mut self, function: &FunctionDefinition) -> String {
+        let is_static = function.flags.contains("Static");
+        let mut out_types = vec![];
+        let mut has_optional = false;
+
+        let mut arguments = if is_static { vec![] } else { vec!["self".to_string()] };
+        for argument in function.arguments.iter() {
+            let type_ = self.python_type(&argument.type_);
+            has_optional |= argument.is_out_param;
+
+            if argument.is_out_param {
+                out_types.push(type_.clone());
+            }
+
+            if has_optional {
+                arguments.push(format!("{}: {} = ...", py_identifier(&argument.name), type_));
+            } else {
+                arguments.push(format!("{}: {}", py_identifier(&argument.name), type_));
+            }
+        }
+
+        let return_type = self.python_type(&function.return_value);
+        let return_type = if out_types.is_empty() {
+            return_type
+        } else {
+            let values = (return_type != "None")
+                .then_some(return_type)
+                .into_iter()
+                .chain(out_types)
+                .collect::<Vec<_>>();
+            format!("tuple[{}]", values.join(", "))
+        };
+
+        let mut result = String::new();
+        if is_static {
+            result.push_str("    @staticmethod\n");
+        }
+        writeln!(
+            result,
+            "    def {}({}) -> {}: ...",
+            py_identifier(&function.name),
+            arguments.join(", "),
+            return_type
+        )
+        .unwrap();
+
+        result
+    }
+
+    fn write_enum(&mut self, def: &EnumDefinition) -> bool {
+        if !self.declare(def.name.clone()) {
+            return false;
+        }
+
+        let base = if def.name.ends_with("Flags") { "IntFlag" } else { "IntEnum" };
+        writeln!(self.body, "\nclass {}({}):", def.name, base).unwrap();
+        for (name, value) in def.options.iter() {
+            writeln!(self.body, "    {} = {}", py_identifier(name), value).unwrap();
+        }
+
+        if def.options.is_empty() {
+            self.body.push_str("    ...\n");
+        }
+
+        true
+    }
+}
+
+fn is_struct(def: &StructDefinition) -> bool {
+    def.name.starts_with('F')
+}
+
+/// Classes and structs are exposed without their prefix.
+fn python_name(name: &str) -> String {
+    py_identifier(&name[1..])
+}
+
+fn module_name(package: Option<&str>) -> String {
+    package.map(py_identifier).unwrap_or(UNASSIGNED_MODULE.to_string())
+}
+
+fn py_identifier(name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|it| if it.is_ascii_alphanumeric() || it == '_' { it } else { '_' })
+        .collect::<String>();
+
+    if name.is_empty() || name.starts_with(|it: char| it.is_ascii_digit()) {
+        format!("_{}", name)
+    } else if PYTHON_KEYWORDS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ArgumentDefinition, EnumKind, FieldDefinition, FunctionDump, Manifest, StructDump};
+
+    #[test]
+    fn test_stubs() {
+        let mut lookup = ClassLookup::new(
+            Manifest {
+                packages: HashSet::from(["Engine".to_string(), "MyGame".to_string()]),
+                structs: HashMap::from([
+                    ("Actor".to_string(), "Engine".to_string()),
+                    ("MyCharacter".to_string(), "MyGame".to_string()),
+                    ("EMyState".to_string(), "MyGame".to_string()),
+                ]),
+            },
+            PackageFilter::default(),
+        );
+
+        let actor = StructDefinition {
+            name: "AActor".into(),
+            parents: vec![],
+            struct_size: 8,
+            fields: vec![],
+            package: None,
+            functions: vec![],
+            has_layout: true,
+        };
+        let character = StructDefinition {
+            name: "AMyCharacter".into(),
+            parents: vec!["AActor".into()],
+            struct_size: 16,
+            fields: vec![
+                FieldDefinition::new(
+                    "State".into(),
+                    8,
+                    1,
+                    1,
+                    None,
+                    TypeSignature::new_simple("EMyState".into(), FieldKind::Enum),
+                ),
+                FieldDefinition::new(
+                    "bIsHero".into(),
+                    9,
+                    1,
+                    1,
+                    Some(0),
+                    TypeSignature::new_simple("u8".into(), FieldKind::Primitive),
+                ),
+            ],
+            package: None,
+            functions: vec![],
+            has_layout: true,
+        };
+        lookup.add_struct_dump(StructDump { data: vec![actor, character] });
+        lookup.add_function_dump(FunctionDump {
+            data: HashMap::from([(
+                "AMyCharacter".to_string(),
+                vec![FunctionDefinition {
+                    name: "GetTarget".into(),
+                    return_value: TypeSignature::new_simple("bool".into(), FieldKind::Primitive),
+                    arguments: vec![ArgumentDefinition {
+                        name: "Target".into(),
+                        is_out_param: true,
+                        type_: TypeSignature::new_pointer("AActor".into(), FieldKind::Class),
+                    }],
+                    flags: "Final|Native".into(),
+                    offset: 0,
+                }],
+            )]),
+        });
+        lookup.add_enum_dump(crate::EnumDump {
+            data: vec![EnumDefinition {
+                name: "EMyState".into(),
+                kind: EnumKind::U8,
+                options: vec![("None".into(), 0), ("Active".into(), 1)],
+                package: None,
+            }],
+        });
+
+        let stubs = StubGenerator::new(&lookup).generate();
+
+        assert_eq!(
+            stubs[Path::new("unreal_engine/packages/my_game.pyi")],
+            "# Generated from the Dumper-7 dump, do not edit.
+from enum import IntEnum, IntFlag
+from typing import Any
+from unreal_engine.packages.engine import Actor
+
+class MyCharacter(Actor):
+    State: EMyState
+    bIsHero: bool
+    def GetTarget(self, Target: Actor = ...) -> tuple[bool, Actor]: ...
+
+class EMyState(IntEnum):
+    None_ = 0
+    Active = 1
+"
+        );
+        assert_eq!(
+            stubs[Path::new("unreal_engine/classes.pyi")],
+            "# Generated from the Dumper-7 dump, do not edit.
+from unreal_engine.packages.engine import Actor as Actor
+from unreal_engine.packages.my_game import MyCharacter as MyCharacter
+"
+        );
+    }
+}
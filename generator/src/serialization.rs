@@ -1,14 +1,34 @@
-use crate::{ArgumentDefinition, EnumDefinition, EnumDump, EnumKind, FieldKind, FunctionDefinition, FunctionDump, StructDefinition, StructDump, TypeSignature};
+use crate::{ArgumentDefinition, Diagnostic, Dump, DumpFormat, EnumDefinition, EnumDump, EnumKind, FieldKind, FunctionDefinition, FunctionDump, Manifest, StructDefinition, StructDump, TypeSignature};
 use proc_macro2::Ident;
 use serde::Deserialize;
-use std::collections::{HashMap, HashSet};
-use std::io::Read;
-use anyhow::Context;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use anyhow::{bail, Context};
 use syn::parse_str;
 
+/// Versions of the Dumpspace JSON format written by Dumper-7 that the readers below understand.
+///
+/// Every file of a dump carries the version, so a layout change is caught before any entry is misread.
+pub const SUPPORTED_VERSIONS: &[u32] = &[10202];
+
+fn check_version(version: Option<u32>) -> anyhow::Result<u32> {
+    match version {
+        Some(version) if SUPPORTED_VERSIONS.contains(&version) => Ok(version),
+        Some(version) => bail!(
+            "Unsupported Dumper-7 format version {} (supported: {:?}), the readers in serialization.rs need to learn its layout",
+            version,
+            SUPPORTED_VERSIONS
+        ),
+        None => bail!("Missing Dumper-7 format version, the dump predates the supported versions {:?}", SUPPORTED_VERSIONS),
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RawEnumDump {
     pub data: Vec<HashMap<String, (Vec<HashMap<String, i64>>, String)>>,
+    pub version: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -18,6 +38,8 @@ pub enum FieldDefinition {
     MDKClassSize(usize),
     Field((FieldSignature, usize, usize, usize)),
     FieldWithBitOffset((FieldSignature, usize, usize, usize, u8)),
+    /// Bitfield with an explicit bit count, for dumpers that export it
+    FieldWithBitWidth((FieldSignature, usize, usize, usize, u8, u8)),
 }
 
 #[derive(Deserialize, Debug)]
@@ -39,23 +61,32 @@ struct MDKClassSize {
 }
 
 
+/// Entries are only parsed one by one, so that an unexpected one does not fail the whole file.
 #[derive(Deserialize, Debug)]
 pub struct JsonData {
-    pub data: Vec<HashMap<String, Vec<HashMap<String, FieldDefinition>>>>,
+    pub data: Vec<HashMap<String, Vec<HashMap<String, serde_json::Value>>>>,
     pub updated_at: String,
+    pub version: Option<u32>,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct FunctionData {
-    pub data: Vec<HashMap<String, Vec<HashMap<String, FunctionSignature>>>>,
+    pub data: Vec<HashMap<String, Vec<HashMap<String, serde_json::Value>>>>,
+    pub version: Option<u32>,
 }
 
-#[derive(Deserialize, Debug, Eq, PartialEq)]
+#[derive(Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct OffsetData {
     pub data: Vec<Offset>,
 }
 
-#[derive(Deserialize, Debug, Eq, PartialEq)]
+#[derive(Deserialize, Debug)]
+struct RawOffsetData {
+    data: Vec<Offset>,
+    version: Option<u32>,
+}
+
+#[derive(Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct Offset(
     pub String,
     pub usize,
@@ -76,82 +107,219 @@ pub struct FunctionArgument {
     pub name: String,
 }
 
+impl Dump {
+    /// Reads the five JSON files of a Dumper-7 dump, failing if their format version is not supported.
+    ///
+    /// Entries that cannot be read are skipped and reported in [Dump::diagnostics].
+    pub fn load<P: AsRef<Path>>(base_path: P) -> anyhow::Result<Self> {
+        let base_path = base_path.as_ref();
+        let mut diagnostics = vec![];
+
+        let offsets: RawOffsetData = serde_json::from_reader(
+            File::open(base_path.join("OffsetsInfo.json")).context("Offsets")?,
+        )
+        .context("Unable to read OffsetsInfo.json")?;
+        let version = check_version(offsets.version).context("Unable to read OffsetsInfo.json")?;
+
+        let classes = StructDump::from_raw_json(
+            File::open(base_path.join("ClassesInfo.json")).context("ClassesInfo")?,
+            &mut diagnostics,
+        )
+        .context("Unable to read ClassesInfo.json")?;
+        let structs = StructDump::from_raw_json(
+            File::open(base_path.join("StructsInfo.json")).context("StructsInfo")?,
+            &mut diagnostics,
+        )
+        .context("Unable to read StructsInfo.json")?;
+        let enums = EnumDump::from_raw_json(
+            File::open(base_path.join("EnumsInfo.json")).context("EnumsInfo")?,
+            &mut diagnostics,
+        )
+        .context("Unable to read EnumsInfo.json")?;
+        let functions = FunctionDump::from_raw_json(
+            File::open(base_path.join("FunctionsInfo.json")).context("Functions")?,
+            &mut diagnostics,
+        )
+        .context("Unable to read FunctionsInfo.json")?;
+
+        let format = DumpFormat {
+            version,
+            marks_out_params: functions.marks_out_params(),
+        };
+        if !format.marks_out_params {
+            let affected = functions.data.values().flatten().filter(|it| it.flags.contains("HasOutParams")).count();
+            if affected > 0 {
+                diagnostics.push(Diagnostic::new(
+                    "FunctionsInfo.json",
+                    None,
+                    format!("out params are not marked (stock Dumper-7), the params of {} functions with out params are treated as inputs", affected),
+                ));
+            }
+        }
+
+        Ok(Self {
+            classes,
+            structs,
+            enums,
+            functions,
+            offsets: OffsetData { data: offsets.data },
+            format,
+            diagnostics,
+        })
+    }
+}
+
+/// Takes the single `name: value` pair that Dumper-7 wraps every entry in.
+fn single_entry<V>(map: HashMap<String, V>, diagnostics: &mut Vec<Diagnostic>) -> Option<(String, V)> {
+    if map.len() != 1 {
+        let names = map.keys().map(|it| it.as_str()).collect::<Vec<_>>().join(", ");
+        diagnostics.push(Diagnostic::new(
+            "<entry>",
+            None,
+            format!("expected a single name per entry, found [{}]", names),
+        ));
+        return None;
+    }
+
+    map.into_iter().next()
+}
+
+/// Compact manifest format that can be checked in next to the JSON dumps: package name to type names.
+type RawManifest = BTreeMap<String, BTreeSet<String>>;
+
+impl Manifest {
+    pub fn from_json<R>(source: R) -> anyhow::Result<Self>
+    where
+        R: Read,
+    {
+        let raw: RawManifest = serde_json::from_reader(source).context("Unable to read manifest")?;
+        let mut result = Self {
+            structs: HashMap::new(),
+            packages: HashSet::new(),
+        };
+
+        for (package, names) in raw {
+            for name in names {
+                if let Some(old_package) = result.structs.insert(name.clone(), package.clone()) {
+                    eprintln!("Duplicate entry: name={} packages={}, {}", name, old_package, package);
+                }
+            }
+
+            result.packages.insert(package);
+        }
+
+        Ok(result)
+    }
+
+    pub fn to_json<W>(&self, target: W) -> anyhow::Result<()>
+    where
+        W: Write,
+    {
+        let mut raw: RawManifest = self.packages.iter()
+            .map(|package| (package.clone(), BTreeSet::new()))
+            .collect();
+
+        for (name, package) in self.structs.iter() {
+            raw.entry(package.clone()).or_default().insert(name.clone());
+        }
+
+        serde_json::to_writer_pretty(target, &raw).context("Unable to write manifest")
+    }
+}
+
 impl EnumDump {
-    pub fn from_raw_json<R>(source: R) -> anyhow::Result<Self>
+    pub fn from_raw_json<R>(source: R, diagnostics: &mut Vec<Diagnostic>) -> anyhow::Result<Self>
     where
         R: Read,
     {
         let raw: RawEnumDump = serde_json::from_reader(source).context("Unable to read enum dump")?;
+        check_version(raw.version)?;
 
         Ok(Self {
-            data: raw.data.into_iter().map(|enum_type| {
-                let (enum_name, enum_def) = enum_type.into_iter().nth(0).unwrap();
+            data: raw.data.into_iter().filter_map(|enum_type| {
+                let (enum_name, enum_def) = single_entry(enum_type, diagnostics)?;
                 let kind = match enum_def.1.as_str() {
                     "uint8" | "uint8_t" => EnumKind::U8,
                     "uint16" | "uint16_t" => EnumKind::U16,
                     "uint32" | "uint32_t" => EnumKind::U32,
                     "uint64" | "uint64_t" => EnumKind::U64,
-                    &_ => panic!("Invalid enum kind: {}", enum_def.1)
+                    other => {
+                        diagnostics.push(Diagnostic::new(
+                            &enum_name,
+                            None,
+                            format!("unknown underlying type `{}`, skipping the enum", other),
+                        ));
+                        return None;
+                    }
                 };
 
-                let max_val = kind.max_val();
-                let mut taken = HashSet::new();
-                let mut taken_names = HashSet::new();
-                let mut options = vec![];
+                let options = enum_def.0.into_iter()
+                    .filter_map(|it| single_entry(it, diagnostics))
+                    .map(|(option_name, value)| (option_name.replace(&format!("{}__", enum_name), ""), value));
 
-                for it in enum_def.0 {
-                    let (mut option_name, option_value) = it.into_iter().nth(0).unwrap();
+                Some(enum_definition(&enum_name, kind, options))
+            }).collect()
+        })
+    }
+}
 
-                    option_name = option_name.replace(&format!("{}__", enum_name), "");
-                    if parse_str::<Ident>(option_name.as_str()).is_err() {
-                        option_name += "_";
-                    }
 
-                    let mut counter = 0;
-                    let base_name = option_name.clone();
-                    while !taken_names.insert(option_name.clone()) {
-                        option_name += &format!("{}_{}", base_name, counter);
-                        counter += 1;
-                    }
+/// Turns the options of an enum into valid, unique identifiers and drops the values that do not fit its underlying type.
+///
+/// Negative values (usually a `_MAX` option of a signed enum) become the maximum of the underlying type.
+pub(crate) fn enum_definition(enum_name: &str, kind: EnumKind, raw_options: impl IntoIterator<Item = (String, i64)>) -> EnumDefinition {
+    let max_val = kind.max_val();
+    let mut taken = HashSet::new();
+    let mut taken_names = HashSet::new();
+    let mut options = vec![];
 
+    for (mut option_name, option_value) in raw_options {
+        if parse_str::<Ident>(option_name.as_str()).is_err() {
+            option_name += "_";
+        }
 
-                    let option = if option_value < 0 {
-                        Some((option_name, max_val))
-                    } else if option_value <= max_val as i64 {
-                        Some((option_name, option_value as u64))
-                    } else {
-                        None
-                    };
+        let mut counter = 0;
+        let base_name = option_name.clone();
+        while !taken_names.insert(option_name.clone()) {
+            option_name += &format!("{}_{}", base_name, counter);
+            counter += 1;
+        }
 
-                    if let Some(option) = option {
-                        if taken.insert(option.1) {
-                            options.push(option);
-                        }
-                    }
-                }
 
-                EnumDefinition {
-                    name: enum_name.replace(":", "_"),
-                    kind,
-                    options,
-                    package: None,
-                }
-            }).collect()
-        })
+        let option = if option_value < 0 {
+            Some((option_name, max_val))
+        } else if option_value <= max_val as i64 {
+            Some((option_name, option_value as u64))
+        } else {
+            None
+        };
+
+        if let Some(option) = option {
+            if taken.insert(option.1) {
+                options.push(option);
+            }
+        }
     }
-}
 
+    EnumDefinition {
+        name: enum_name.replace(":", "_"),
+        kind,
+        options,
+        package: None,
+    }
+}
 
 impl StructDump {
-    pub fn from_raw_json<R>(source: R) -> anyhow::Result<Self>
+    pub fn from_raw_json<R>(source: R, diagnostics: &mut Vec<Diagnostic>) -> anyhow::Result<Self>
     where
         R: Read,
     {
         let raw: JsonData = serde_json::from_reader(source).context("Unable to read struct dump")?;
+        check_version(raw.version)?;
 
         Ok(StructDump {
-            data: raw.data.into_iter().map(|map| {
-                let (name, description) = map.into_iter().nth(0).unwrap();
+            data: raw.data.into_iter().filter_map(|map| {
+                let (name, description) = single_entry(map, diagnostics)?;
                 let mut result = StructDefinition {
                     name: name.replace(":", "_"),
                     parents: vec![],
@@ -159,93 +327,143 @@ impl StructDump {
                     fields: vec![],
                     package: None,
                     functions: vec![],
+                    has_layout: true,
                 };
 
-                for (field_name, definition) in description.into_iter().flatten() {
-                    match definition {
+                for (field_name, value) in description.into_iter().flatten() {
+                    // Skipped fields end up in the padding, so the layout of the struct is unaffected
+                    let definition = match serde_json::from_value::<FieldDefinition>(value) {
+                        Ok(definition) => definition,
+                        Err(_) => {
+                            diagnostics.push(Diagnostic::new(&name, Some(&field_name), "unexpected field layout, skipping the field"));
+                            continue;
+                        }
+                    };
+
+                    let (signature, offset, size, unknown, bit_offset, bit_width) = match definition {
                         FieldDefinition::InheritInfo(classes) => {
                             result.parents = classes;
+                            continue;
                         }
                         FieldDefinition::MDKClassSize(size) => {
                             result.struct_size = size;
+                            continue;
                         }
-                        FieldDefinition::Field(def) => {
-                            result.fields.push(crate::FieldDefinition::new(
-                                field_name,
-                                def.1,
-                                def.2,
-                                def.3,
-                                None,
-                                def.0.into(),
-                            ));
-                        }
-                        FieldDefinition::FieldWithBitOffset(def) => {
-                            result.fields.push(crate::FieldDefinition::new(
-                                field_name,
-                                def.1,
-                                def.2,
-                                def.3,
-                                Some(def.4),
-                                def.0.into(),
-                            ));
+                        FieldDefinition::Field(def) => (def.0, def.1, def.2, def.3, None, None),
+                        FieldDefinition::FieldWithBitOffset(def) => (def.0, def.1, def.2, def.3, Some(def.4), None),
+                        FieldDefinition::FieldWithBitWidth(def) => (def.0, def.1, def.2, def.3, Some(def.4), Some(def.5)),
+                    };
+
+                    let signature = match TypeSignature::try_from(signature) {
+                        Ok(signature) => signature,
+                        Err(reason) => {
+                            diagnostics.push(Diagnostic::new(&name, Some(&field_name), format!("{}, skipping the field", reason)));
+                            continue;
                         }
+                    };
+
+                    let mut field = crate::FieldDefinition::new(field_name, offset, size, unknown, bit_offset, signature);
+                    if let Some(bit_width) = bit_width {
+                        field.bit_width = bit_width;
                     }
+                    result.fields.push(field);
                 }
 
-                result
+                Some(result)
             }).collect()
         })
     }
 }
 
 impl FunctionDump {
-    pub fn from_raw_json<R>(source: R) -> anyhow::Result<Self>
+    pub fn from_raw_json<R>(source: R, diagnostics: &mut Vec<Diagnostic>) -> anyhow::Result<Self>
     where
         R: Read,
     {
         let raw: FunctionData = serde_json::from_reader(source).context("Unable to read function dump")?;
+        check_version(raw.version)?;
 
         Ok(FunctionDump {
-            data: raw.data.into_iter().map(|map| {
-                let (class_name, functions) = map.into_iter().nth(0).unwrap();
+            data: raw.data.into_iter().filter_map(|map| {
+                let (class_name, functions) = single_entry(map, diagnostics)?;
                 let function_defs = functions.into_iter()
-                    .map(|fun| {
-                        let (fun_name, sig) = fun.into_iter().nth(0).unwrap();
-
-                        FunctionDefinition {
-                            name: fun_name,
-                            return_value: sig.return_value.into(),
-                            arguments: sig.arguments.into_iter().map(|it| it.into()).collect(),
-                            flags: sig.flags,
-                            offset: sig.offset,
+                    .filter_map(|fun| {
+                        let (fun_name, value) = single_entry(fun, diagnostics)?;
+                        let definition = serde_json::from_value::<FunctionSignature>(value)
+                            .map_err(|_| "unexpected function layout".to_string())
+                            .and_then(|sig| FunctionDefinition::try_from((fun_name.clone(), sig)));
+
+                        match definition {
+                            Ok(definition) => Some(definition),
+                            Err(reason) => {
+                                diagnostics.push(Diagnostic::new(&class_name, Some(&fun_name), format!("{}, skipping the function", reason)));
+                                None
+                            }
                         }
-                    });
+                    })
+                    .collect();
 
-
-                (class_name, function_defs.collect())
+                Some((class_name, function_defs))
             }).collect()
         })
     }
+
+    /// Whether any argument is marked as out param, which only the modified Dumper-7 does.
+    ///
+    /// Stock Dumper-7 writes the same `[type, reference, name]` arguments, but always leaves the reference empty.
+    pub fn marks_out_params(&self) -> bool {
+        self.data.values().flatten().flat_map(|it| &it.arguments).any(|it| it.is_out_param)
+    }
 }
 
-impl From<FunctionArgument> for ArgumentDefinition {
-    fn from(value: FunctionArgument) -> Self {
-        ArgumentDefinition {
+impl TryFrom<(String, FunctionSignature)> for FunctionDefinition {
+    type Error = String;
+
+    fn try_from((name, sig): (String, FunctionSignature)) -> Result<Self, Self::Error> {
+        Ok(FunctionDefinition {
+            name,
+            return_value: sig.return_value.try_into()?,
+            arguments: sig.arguments.into_iter().map(|it| it.try_into()).collect::<Result<_, _>>()?,
+            flags: sig.flags,
+            offset: sig.offset,
+        })
+    }
+}
+
+impl TryFrom<FunctionArgument> for ArgumentDefinition {
+    type Error = String;
+
+    fn try_from(value: FunctionArgument) -> Result<Self, Self::Error> {
+        Ok(ArgumentDefinition {
             name: value.name,
             is_out_param: value.reference == "&",
-            type_: value.type_.into(),
-        }
+            type_: value.type_.try_into()?,
+        })
     }
 }
 
-impl From<FieldSignature> for TypeSignature {
-    fn from(value: FieldSignature) -> Self {
+impl TryFrom<FieldSignature> for TypeSignature {
+    type Error = String;
+
+    fn try_from(value: FieldSignature) -> Result<Self, Self::Error> {
         if value.0 == "TEnumAsByte" && value.3.len() == 1 {
             let generics = value.3;
-            return generics.into_iter().nth(0).unwrap().into();
+            return generics.into_iter().nth(0).unwrap().try_into();
         }
 
-        Self {
+        Ok(Self {
+            kind: match value.1.as_str() {
+                "S" => FieldKind::Struct,
+                "C" => FieldKind::Class,
+                "E" => FieldKind::Enum,
+                "D" => FieldKind::Primitive,
+                other => return Err(format!("unknown data type `{}` of `{}`", other, value.0)),
+            },
+            is_pointer: match value.2.as_str() {
+                "*" => true,
+                "" => false,
+                other => return Err(format!("unknown keyword `{}` of `{}`", other, value.0)),
+            },
             name: match value.0.as_str() {
                 "float" => "f32".to_string(),
                 "double" => "f64".to_string(),
@@ -263,20 +481,8 @@ impl From<FieldSignature> for TypeSignature {
                 "unsigned char" => "u8".to_string(),
                 name => name.replace(":", "_"), // handle other types as needed
             },
-            kind: match value.1.as_str() {
-                "S" => FieldKind::Struct,
-                "C" => FieldKind::Class,
-                "E" => FieldKind::Enum,
-                "D" => FieldKind::Primitive,
-                _ => panic!("Unknown data type: {}", value.1)
-            },
-            is_pointer: match value.2.as_str() {
-                "*" => true,
-                "" => false,
-                _ => panic!("Unknown keyword: {}", value.2)
-            },
-            generics: value.3.into_iter().map(|it| it.into()).collect(),
-        }
+            generics: value.3.into_iter().map(|it| it.try_into()).collect::<Result<_, _>>()?,
+        })
     }
 }
 
@@ -300,7 +506,112 @@ mod tests {
         });
     }
     #[test]
+    fn test_manifest() {
+        let manifest = Manifest::from_json(r#"{"CoreUObject": ["Object", "Class"], "Empty": []}"#.as_bytes()).unwrap();
 
+        assert_eq!(manifest.packages, HashSet::from(["CoreUObject".to_string(), "Empty".to_string()]));
+        assert_eq!(manifest.structs.get("Class").map(|it| it.as_str()), Some("CoreUObject"));
+
+        let mut output = vec![];
+        manifest.to_json(&mut output).unwrap();
+        let round_trip = Manifest::from_json(output.as_slice()).unwrap();
+
+        assert_eq!(round_trip.packages, manifest.packages);
+        assert_eq!(round_trip.structs, manifest.structs);
+    }
+
+    #[test]
+    fn test_bitfields() {
+        let dump = StructDump::from_raw_json(r#"{
+            "updated_at": "0",
+            "version": 10202,
+            "data": [{"UMyObject": [
+                {"bFlag": [["uint8", "D", "", []], 40, 1, 1, 2]},
+                {"Mode": [["uint8", "D", "", []], 40, 1, 1, 3, 2]}
+            ]}]}"#.as_bytes(), &mut vec![]).unwrap();
+
+        let fields = &dump.data[0].fields;
+        assert_eq!((fields[0].bit_offset, fields[0].bit_width), (Some(2), 1));
+        assert_eq!((fields[1].bit_offset, fields[1].bit_width), (Some(3), 2));
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let mut diagnostics = vec![];
+        let structs = StructDump::from_raw_json(r#"{
+            "updated_at": "0",
+            "version": 10202,
+            "data": [{"UMyObject": [
+                {"__InheritInfo": ["UObject"]},
+                {"Health": [["int32", "D", "", []], 40, 4, 1]},
+                {"Weird": [["int32", "X", "", []], 44, 4, 1]},
+                {"Ref": [["UObject", "C", "&", []], 48, 8, 1]},
+                {"Broken": "nope"}
+            ]}]}"#.as_bytes(), &mut diagnostics).unwrap();
+        let enums = EnumDump::from_raw_json(r#"{
+            "version": 10202,
+            "data": [
+                {"EMyEnum": [[{"First": 0}], "uint8"]},
+                {"ESigned": [[{"Negative": -1}], "int8"]}
+            ]}"#.as_bytes(), &mut diagnostics).unwrap();
+        let functions = FunctionDump::from_raw_json(r#"{
+            "version": 10202,
+            "data": [{"UMyObject": [
+                {"GetHealth": [["int32", "D", "", []], [], 0, "Final|Native"]},
+                {"GetWeird": [["int32", "D", "", []], [[["int32", "X", "", []], "", "Value"]], 0, "Final|Native"]}
+            ]}]}"#.as_bytes(), &mut diagnostics).unwrap();
+
+        let fields = structs.data[0].fields.iter().map(|it| it.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(fields, vec!["Health"]);
+        assert_eq!(structs.data[0].parents, vec!["UObject"]);
+        assert_eq!(enums.data.iter().map(|it| it.name.as_str()).collect::<Vec<_>>(), vec!["EMyEnum"]);
+        assert_eq!(functions.data["UMyObject"].iter().map(|it| it.name.as_str()).collect::<Vec<_>>(), vec!["GetHealth"]);
+
+        assert_eq!(
+            diagnostics.iter().map(|it| it.to_string()).collect::<Vec<_>>(),
+            vec![
+                "UMyObject::Weird: unknown data type `X` of `int32`, skipping the field",
+                "UMyObject::Ref: unknown keyword `&` of `UObject`, skipping the field",
+                "UMyObject::Broken: unexpected field layout, skipping the field",
+                "ESigned: unknown underlying type `int8`, skipping the enum",
+                "UMyObject::GetWeird: unknown data type `X` of `int32`, skipping the function",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_versions() {
+        let read = |json: &str| EnumDump::from_raw_json(json.as_bytes(), &mut vec![]).map_err(|it| it.to_string());
+
+        assert!(read(r#"{"version": 10202, "data": []}"#).is_ok());
+        assert_eq!(
+            read(r#"{"version": 10300, "data": []}"#).err().unwrap(),
+            "Unsupported Dumper-7 format version 10300 (supported: [10202]), the readers in serialization.rs need to learn its layout"
+        );
+        assert!(read(r#"{"data": []}"#).unwrap_err().starts_with("Missing Dumper-7 format version"));
+    }
+
+    #[test]
+    fn test_out_params() {
+        let read = |reference: &str| {
+            let json = format!(r#"{{
+                "version": 10202,
+                "data": [{{"UMyObject": [
+                    {{"GetValue": [["void", "D", "", []], [[["int32", "D", "", []], "{}", "OutValue"]], 0, "Final|Native|HasOutParams"]}}
+                ]}}]}}"#, reference);
+            FunctionDump::from_raw_json(json.as_bytes(), &mut vec![]).unwrap()
+        };
+
+        let modified = read("&");
+        assert!(modified.marks_out_params());
+        assert!(modified.data["UMyObject"][0].arguments[0].is_out_param);
+
+        let stock = read("");
+        assert!(!stock.marks_out_params());
+        assert!(!stock.data["UMyObject"][0].arguments[0].is_out_param);
+    }
+
+    #[test]
     fn test_functions() {
         let result: FunctionData = serde_json::from_reader(File::open("../manasdk/dump/FunctionsInfo.json").unwrap()).unwrap();
     }
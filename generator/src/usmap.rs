@@ -0,0 +1,488 @@
+use crate::serialization::enum_definition;
+use crate::{Diagnostic, EnumDump, EnumKind, FieldDefinition, FieldKind, StructDefinition, StructDump, TypeSignature};
+use anyhow::{bail, Context};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+const MAGIC: u16 = 0x30C4;
+
+const VERSION_PACKAGE_VERSIONING: u8 = 1;
+const VERSION_LONG_FNAME: u8 = 2;
+const VERSION_LARGE_ENUMS: u8 = 3;
+const VERSION_EXPLICIT_ENUM_VALUES: u8 = 4;
+
+/// The latest `.usmap` version the reader understands.
+pub const LATEST_USMAP_VERSION: u8 = VERSION_EXPLICIT_ENUM_VALUES;
+
+const COMPRESSION_NONE: u8 = 0;
+const COMPRESSION_OODLE: u8 = 1;
+const COMPRESSION_BROTLI: u8 = 2;
+const COMPRESSION_ZSTD: u8 = 3;
+
+/// The types of an unversioned property mappings (`.usmap`) file, as written by Dumper-7, UE4SS and similar tools.
+///
+/// Mappings only carry the names and types of the properties, so every struct is flagged with
+/// [StructDefinition::has_layout] unset and has neither a size nor field offsets.
+/// Since they don't distinguish classes from structs either, the `U`/`A`/`F` prefixes are derived from the super chain.
+#[derive(Clone, Debug)]
+pub struct Mappings {
+    pub structs: StructDump,
+    pub enums: EnumDump,
+    /// Properties that could not be read and were skipped.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Mappings {
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).with_context(|| format!("Unable to read mappings: {}", path.display()))?;
+
+        Self::from_bytes(&bytes).with_context(|| format!("Unable to read mappings: {}", path.display()))
+    }
+
+    /// Reads the mappings, decompressing them if needed. Oodle compression is not supported, as it has no pure-Rust decoder.
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = Reader::new(bytes);
+
+        let magic = reader.u16()?;
+        if magic != MAGIC {
+            bail!("Not a .usmap file (magic 0x{:04X})", magic);
+        }
+
+        let version = reader.u8()?;
+        if version > LATEST_USMAP_VERSION {
+            bail!("Unsupported .usmap version {} (latest supported: {})", version, LATEST_USMAP_VERSION);
+        }
+
+        if version >= VERSION_PACKAGE_VERSIONING && reader.i32()? != 0 {
+            // Package file versions (UE4 and UE5), custom versions (GUID + version) and the changelist
+            reader.bytes(8)?;
+            let custom_versions = reader.count()?;
+            reader.bytes(custom_versions * 20)?;
+            reader.bytes(4)?;
+        }
+
+        let compression = reader.u8()?;
+        let compressed_size = reader.u32()? as usize;
+        let decompressed_size = reader.u32()? as usize;
+        let compressed = reader.bytes(compressed_size)?;
+
+        let payload = match compression {
+            COMPRESSION_NONE if compressed_size == decompressed_size => compressed.to_vec(),
+            COMPRESSION_NONE => bail!("Uncompressed mappings of {} bytes claim to be {} bytes", compressed_size, decompressed_size),
+            COMPRESSION_OODLE => bail!("Oodle compressed mappings are not supported, export them uncompressed or with Brotli or Zstandard"),
+            COMPRESSION_BROTLI => {
+                let mut payload = Vec::with_capacity(decompressed_size);
+                brotli_decompressor::Decompressor::new(compressed, 4096)
+                    .read_to_end(&mut payload)
+                    .context("Invalid Brotli stream")?;
+                payload
+            }
+            COMPRESSION_ZSTD => {
+                let mut payload = Vec::with_capacity(decompressed_size);
+                let mut source = compressed;
+                ruzstd::StreamingDecoder::new(&mut source)
+                    .map_err(|it| anyhow::anyhow!("Invalid Zstandard stream: {}", it))?
+                    .read_to_end(&mut payload)
+                    .context("Invalid Zstandard stream")?;
+                payload
+            }
+            other => bail!("Unknown compression method {}", other),
+        };
+
+        if payload.len() != decompressed_size {
+            bail!("Mappings decompressed to {} bytes instead of {}", payload.len(), decompressed_size);
+        }
+
+        Self::read_payload(&mut Reader::new(&payload), version)
+    }
+
+    fn read_payload(reader: &mut Reader, version: u8) -> anyhow::Result<Self> {
+        let name_count = reader.count()?;
+        let names = (0..name_count)
+            .map(|_| {
+                let length = if version >= VERSION_LONG_FNAME { reader.u16()? as usize } else { reader.u8()? as usize };
+                Ok(String::from_utf8_lossy(reader.bytes(length)?).into_owned())
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let names = Names(names);
+
+        let enum_count = reader.count()?;
+        let mut enums = Vec::with_capacity(enum_count);
+        for _ in 0..enum_count {
+            let enum_name = names.get(reader.i32()?)?;
+            let option_count = if version >= VERSION_LARGE_ENUMS { reader.u16()? as usize } else { reader.u8()? as usize };
+
+            let mut options = Vec::with_capacity(option_count);
+            for index in 0..option_count {
+                let value = if version >= VERSION_EXPLICIT_ENUM_VALUES { reader.i64()? } else { index as i64 };
+                let option_name = names.get(reader.i32()?)?;
+                // Enum classes are stored with their scope, e.g. `EMyEnum::First`
+                options.push((option_name.rsplit("::").next().unwrap_or(option_name).to_string(), value));
+            }
+
+            // The underlying type is not part of the mappings, so it is the smallest one that fits the values
+            let max_value = options.iter().map(|it| it.1).max().unwrap_or_default();
+            let kind = match max_value {
+                ..=0xFF => EnumKind::U8,
+                0x100..=0xFFFF => EnumKind::U16,
+                0x1_0000..=0xFFFF_FFFF => EnumKind::U32,
+                _ => EnumKind::U64,
+            };
+            enums.push(enum_definition(enum_name, kind, options));
+        }
+
+        let mut diagnostics = vec![];
+        let struct_count = reader.count()?;
+        let mut raw_structs = Vec::with_capacity(struct_count);
+        for _ in 0..struct_count {
+            let name = names.get(reader.i32()?)?.to_string();
+            let parent = names.get_optional(reader.i32()?)?.map(|it| it.to_string());
+            let _property_count = reader.u16()?;
+            let serializable_property_count = reader.u16()?;
+
+            let mut fields = Vec::with_capacity(serializable_property_count as usize);
+            for _ in 0..serializable_property_count {
+                let _schema_index = reader.u16()?;
+                let array_dim = reader.u8()?;
+                let field_name = names.get(reader.i32()?)?;
+
+                // Unknown types carry no data, so the remaining properties can still be read
+                match reader.property_type(&names)? {
+                    Ok(signature) => fields.push(FieldDefinition::new(field_name.to_string(), 0, 0, array_dim as usize, None, signature)),
+                    Err(reason) => diagnostics.push(Diagnostic::new(&name, Some(field_name), format!("{}, skipping the field", reason))),
+                }
+            }
+
+            raw_structs.push((name, parent, fields));
+        }
+
+        let supers: HashMap<_, _> = raw_structs.iter().map(|(name, parent, _)| (name.clone(), parent.clone())).collect();
+        let structs = raw_structs
+            .into_iter()
+            .map(|(name, parent, mut fields)| {
+                let parents = std::iter::successors(parent, |it| supers.get(it).cloned().flatten())
+                    .take(supers.len())
+                    .map(|it| prefixed(&it, &supers))
+                    .collect();
+                for field in fields.iter_mut() {
+                    prefix_structs(&mut field.signature, &supers);
+                }
+
+                StructDefinition {
+                    name: prefixed(&name, &supers),
+                    parents,
+                    struct_size: 0,
+                    fields,
+                    package: None,
+                    functions: vec![],
+                    has_layout: false,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            structs: StructDump { data: structs },
+            enums: EnumDump { data: enums },
+            diagnostics,
+        })
+    }
+}
+
+/// Adds the prefix Unreal uses in C++ to a type name of the mappings: `A` for actors, `U` for other objects and `F` for structs.
+fn prefixed(name: &str, supers: &HashMap<String, Option<String>>) -> String {
+    let mut current = Some(name);
+    // The chain is bounded in case the mappings contain a cycle
+    for _ in 0..=supers.len() {
+        match current {
+            Some("Actor") => return format!("A{}", name),
+            Some("Object") => return format!("U{}", name),
+            Some(it) => current = supers.get(it).and_then(|it| it.as_deref()),
+            None => break,
+        }
+    }
+
+    format!("F{}", name)
+}
+
+fn prefix_structs(signature: &mut TypeSignature, supers: &HashMap<String, Option<String>>) {
+    if signature.kind == FieldKind::Struct && !signature.is_pointer {
+        signature.name = prefixed(&signature.name, supers);
+    }
+
+    for generic in signature.generics.iter_mut() {
+        prefix_structs(generic, supers);
+    }
+}
+
+struct Names(Vec<String>);
+
+impl Names {
+    fn get(&self, index: i32) -> anyhow::Result<&str> {
+        self.get_optional(index)?.context("Unexpected empty name")
+    }
+
+    fn get_optional(&self, index: i32) -> anyhow::Result<Option<&str>> {
+        if index == -1 {
+            return Ok(None);
+        }
+
+        self.0
+            .get(index as usize)
+            .map(|it| Some(it.as_str()))
+            .with_context(|| format!("Name index {} out of range ({} names)", index, self.0.len()))
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn bytes(&mut self, length: usize) -> anyhow::Result<&'a [u8]> {
+        let end = self.position.checked_add(length).filter(|&it| it <= self.data.len());
+        let Some(end) = end else {
+            bail!("Unexpected end of the mappings at byte {} (reading {} bytes)", self.position, length);
+        };
+
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> anyhow::Result<[u8; N]> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> anyhow::Result<u8> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> anyhow::Result<u16> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> anyhow::Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn i32(&mut self) -> anyhow::Result<i32> {
+        Ok(i32::from_le_bytes(self.array()?))
+    }
+
+    fn i64(&mut self) -> anyhow::Result<i64> {
+        Ok(i64::from_le_bytes(self.array()?))
+    }
+
+    fn count(&mut self) -> anyhow::Result<usize> {
+        Ok(self.u32()? as usize)
+    }
+
+    /// Reads a property type, translated to the names Dumper-7 uses in its JSON dumps.
+    ///
+    /// Object properties do not carry their class in the mappings, so they all point to `UObject`.
+    fn property_type(&mut self, names: &Names) -> anyhow::Result<Result<TypeSignature, String>> {
+        let object = || TypeSignature::new_pointer("UObject".into(), FieldKind::Class);
+        let simple = |name: &str| TypeSignature::new_simple(name.into(), FieldKind::Primitive);
+        let generic = |name: &str, kind, generics| TypeSignature {
+            name: String::from(name),
+            kind,
+            is_pointer: false,
+            generics,
+        };
+
+        let id = self.u8()?;
+        let signature = match id {
+            0 => simple("u8"),
+            1 => simple("bool"),
+            2 => simple("i32"),
+            3 => simple("f32"),
+            4 => object(),
+            5 => simple("FName"),
+            6 => simple("FDelegateProperty_"),
+            7 => simple("f64"),
+            8 => match self.property_type(names)? {
+                Ok(inner) => generic("TArray", FieldKind::Class, vec![inner]),
+                Err(reason) => return Ok(Err(reason)),
+            },
+            9 => TypeSignature::new_struct(names.get(self.i32()?)?.to_string()),
+            10 => simple("FString"),
+            11 => simple("FText"),
+            12 => generic("TScriptInterface", FieldKind::Primitive, vec![object()]),
+            // Inline and sparse multicast delegates are not distinguished
+            13 => simple("FMulticastInlineDelegateProperty_"),
+            14 => generic("TWeakObjectPtr", FieldKind::Primitive, vec![object()]),
+            15 => generic("TLazyObjectPtr", FieldKind::Primitive, vec![object()]),
+            16 | 17 => generic("TSoftObjectPtr", FieldKind::Primitive, vec![object()]),
+            18 => simple("u64"),
+            19 => simple("u32"),
+            20 => simple("u16"),
+            21 => simple("i64"),
+            22 => simple("i16"),
+            23 => simple("i8"),
+            24 => {
+                let key = self.property_type(names)?;
+                let value = self.property_type(names)?;
+                match (key, value) {
+                    (Ok(key), Ok(value)) => generic("TMap", FieldKind::Class, vec![key, value]),
+                    (Err(reason), _) | (_, Err(reason)) => return Ok(Err(reason)),
+                }
+            }
+            25 => match self.property_type(names)? {
+                Ok(inner) => generic("TSet", FieldKind::Class, vec![inner]),
+                Err(reason) => return Ok(Err(reason)),
+            },
+            26 => {
+                // The underlying property, which the enum type already implies
+                let _ = self.property_type(names)?;
+                TypeSignature::new_simple(names.get(self.i32()?)?.replace(":", "_"), FieldKind::Enum)
+            }
+            27 => generic("TFieldPath", FieldKind::Primitive, vec![TypeSignature::new_struct("FProperty".into())]),
+            28 => match self.property_type(names)? {
+                Ok(inner) => generic("TOptional", FieldKind::Primitive, vec![inner]),
+                Err(reason) => return Ok(Err(reason)),
+            },
+            29 => simple("FUtf8String"),
+            30 => simple("FAnsiString"),
+            other => return Ok(Err(format!("unknown property type {}", other))),
+        };
+
+        Ok(Ok(signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes mappings in the layout of the latest version.
+    #[derive(Default)]
+    struct Writer {
+        names: Vec<String>,
+        data: Vec<u8>,
+    }
+
+    impl Writer {
+        fn name(&mut self, name: &str) -> &mut Self {
+            let index = match self.names.iter().position(|it| it == name) {
+                Some(index) => index,
+                None => {
+                    self.names.push(name.to_string());
+                    self.names.len() - 1
+                }
+            };
+            self.bytes(&(index as i32).to_le_bytes())
+        }
+
+        fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+            self.data.extend_from_slice(bytes);
+            self
+        }
+
+        fn payload(&self) -> Vec<u8> {
+            let mut result = (self.names.len() as u32).to_le_bytes().to_vec();
+            for name in self.names.iter() {
+                result.extend((name.len() as u16).to_le_bytes());
+                result.extend(name.as_bytes());
+            }
+            result.extend(&self.data);
+            result
+        }
+    }
+
+    fn file(compression: u8, payload: &[u8], decompressed_size: usize) -> Vec<u8> {
+        let mut result = MAGIC.to_le_bytes().to_vec();
+        result.push(LATEST_USMAP_VERSION);
+        result.extend(0i32.to_le_bytes());
+        result.push(compression);
+        result.extend((payload.len() as u32).to_le_bytes());
+        result.extend((decompressed_size as u32).to_le_bytes());
+        result.extend(payload);
+        result
+    }
+
+    fn sample() -> Vec<u8> {
+        let mut writer = Writer::default();
+        // Enums: EMyEnum { First = 0, Second = 5 }
+        writer.bytes(&1u32.to_le_bytes()).name("EMyEnum").bytes(&2u16.to_le_bytes());
+        writer.bytes(&0i64.to_le_bytes()).name("EMyEnum::First");
+        writer.bytes(&5i64.to_le_bytes()).name("EMyEnum::Second");
+
+        // Structs: Object, Actor : Object, MyActor : Actor, MyStruct
+        writer.bytes(&4u32.to_le_bytes());
+        writer.name("Object").bytes(&(-1i32).to_le_bytes()).bytes(&[0, 0, 0, 0]);
+        writer.name("Actor").name("Object").bytes(&[0, 0, 0, 0]);
+        writer.name("MyActor").name("Actor").bytes(&[4, 0, 4, 0]);
+        writer.bytes(&[0, 0, 1]).name("Health").bytes(&[2]);
+        writer.bytes(&[1, 0, 1]).name("Items").bytes(&[24, 10, 9]).name("MyStruct");
+        writer.bytes(&[2, 0, 1]).name("Mode").bytes(&[26, 0]).name("EMyEnum");
+        writer.bytes(&[3, 0, 1]).name("Weird").bytes(&[0xFF]);
+        writer.name("MyStruct").bytes(&(-1i32).to_le_bytes()).bytes(&[1, 0, 1, 0]);
+        writer.bytes(&[0, 0, 3]).name("Target").bytes(&[14]);
+
+        writer.payload()
+    }
+
+    #[test]
+    fn test_mappings() {
+        let payload = sample();
+        let mappings = Mappings::from_bytes(&file(COMPRESSION_NONE, &payload, payload.len())).unwrap();
+
+        let my_enum = &mappings.enums.data[0];
+        assert_eq!(my_enum.name, "EMyEnum");
+        assert_eq!(my_enum.options, vec![("First".to_string(), 0), ("Second".to_string(), 5)]);
+
+        let names = mappings.structs.data.iter().map(|it| it.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["UObject", "AActor", "AMyActor", "FMyStruct"]);
+        assert!(mappings.structs.data.iter().all(|it| !it.has_layout));
+
+        let actor = &mappings.structs.data[2];
+        assert_eq!(actor.parents, vec!["AActor", "UObject"]);
+        assert_eq!(
+            actor.fields.iter().map(|it| format!("{}: {}", it.name, it.signature)).collect::<Vec<_>>(),
+            vec!["Health: i32", "Items: TMap<FString, FMyStruct>", "Mode: EMyEnum"]
+        );
+        assert_eq!(mappings.structs.data[3].fields[0].unknown, 3);
+        assert_eq!(mappings.structs.data[3].fields[0].signature.to_string(), "TWeakObjectPtr<UObject*>");
+
+        assert_eq!(
+            mappings.diagnostics.iter().map(|it| it.to_string()).collect::<Vec<_>>(),
+            vec!["MyActor::Weird: unknown property type 255, skipping the field"]
+        );
+    }
+
+    #[test]
+    fn test_compression() {
+        let payload = sample();
+        let expected = Mappings::from_bytes(&file(COMPRESSION_NONE, &payload, payload.len())).unwrap();
+
+        // A Zstandard frame with a single raw block, and a Brotli stream with a single uncompressed meta-block
+        let mut zstd = vec![0x28, 0xB5, 0x2F, 0xFD, 0xA0];
+        zstd.extend((payload.len() as u32).to_le_bytes());
+        zstd.extend(&(1 | ((payload.len() as u32) << 3)).to_le_bytes()[..3]);
+        zstd.extend(&payload);
+        let mut brotli = (((payload.len() as u32 - 1) << 4) | (1 << 20)).to_le_bytes()[..3].to_vec();
+        brotli.extend(&payload);
+        brotli.push(0x03);
+
+        for compressed in [file(COMPRESSION_ZSTD, &zstd, payload.len()), file(COMPRESSION_BROTLI, &brotli, payload.len())] {
+            let mappings = Mappings::from_bytes(&compressed).unwrap();
+            assert_eq!(mappings.structs.data.len(), expected.structs.data.len());
+            assert_eq!(mappings.enums.data[0].options, expected.enums.data[0].options);
+        }
+
+        let error = Mappings::from_bytes(&file(COMPRESSION_OODLE, &payload, payload.len())).unwrap_err();
+        assert!(error.to_string().starts_with("Oodle compressed mappings are not supported"));
+        assert_eq!(
+            Mappings::from_bytes(&[0xC4, 0x30, 9]).unwrap_err().to_string(),
+            "Unsupported .usmap version 9 (latest supported: 4)"
+        );
+        assert!(Mappings::from_bytes(&file(COMPRESSION_NONE, &payload[..10], 10)).is_err());
+    }
+}
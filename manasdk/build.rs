@@ -1,82 +1,95 @@
-use std::collections::HashSet;
+use generator::{Exclusions, PackageFilter, Roots};
 use regex::Regex;
-use rust_format::{Formatter, PrettyPlease};
-use std::fs::File;
-use std::io::Write;
 use std::path::PathBuf;
 
+/// Packages the hand-written code relies on, which are generated regardless of the enabled features.
+const CORE_PACKAGES: &[&str] = &["core_u_object", "engine", "slate_core"];
+
+/// Features that toggle options of the SDK instead of selecting a package.
+const OPTION_FEATURES: &[&str] = &["default", "native_calls", "pruned"];
+
+/// Builds the pattern of packages to generate from the enabled cargo features.
+/// Every package has a feature of the same name, `full` generates all of them.
+fn package_pattern() -> Option<Regex> {
+    let features: Vec<_> = std::env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(|it| it.to_lowercase()))
+        .collect();
+
+    if features.iter().any(|it| it == "full") {
+        return None;
+    }
+
+    let packages = CORE_PACKAGES
+        .iter()
+        .map(|it| it.to_string())
+        .chain(features.into_iter().filter(|it| !OPTION_FEATURES.contains(&it.as_str())))
+        .map(|it| regex::escape(&it))
+        .collect::<Vec<_>>()
+        .join("|");
+
+    Some(Regex::new(&format!("^(?:{})$", packages)).unwrap())
+}
+
 fn main() {
     let out_dir: PathBuf = std::env::var("OUT_DIR").unwrap().try_into().unwrap();
     let output_path = out_dir.join("generated_code");
 
-    std::fs::create_dir_all(&output_path).unwrap();
+    let exclusions: Exclusions = std::fs::read_to_string("exclusions.txt")
+        .expect("Failed to read exclusions")
+        .parse()
+        .expect("Failed to parse exclusions");
 
-    let exclusions = vec![
-        ("UObject", Some("core_u_object")),
-        ("UClass", Some("core_u_object")),
-        ("UProperty", Some("core_u_object")),
-        ("FProperty", None),
-        ("UFunction", Some("core_u_object")),
-        ("UStruct", Some("core_u_object")),
-        ("UField", Some("core_u_object")),
-        ("TArray", None),
-        ("TSoftClassPtr", None),
-        ("TLazyObjectPtr", None),
-        ("TSoftObjectPtr", None),
-        ("TWeakObjectPtr", None),
-        ("FWeakObjectPtr", None),
-        ("TMap", None),
-        ("TSet", None),
-        ("FText", None),
-        ("FString", None),
-        ("FName", None),
-        ("FInputKeyEventArgs", Some("engine")),
-        ("FKey", Some("input_core")),
-    ];
-    
-    let excluded_classes = exclusions.iter().map(|it| it.0).collect::<Vec<_>>();
-    let modified_packages = exclusions.into_iter().filter_map(|it| it.1).collect::<HashSet<_>>();
+    // Only generates what roots.txt references in full, the rest as opaque blobs
+    let roots: Roots = if std::env::var("CARGO_FEATURE_PRUNED").is_ok() {
+        std::fs::read_to_string("roots.txt")
+            .expect("Failed to read roots")
+            .parse()
+            .expect("Failed to parse roots")
+    } else {
+        Roots::default()
+    };
 
-    let definitions = generator::generate_code(
+    let code = generator::generate_code(
         "dump",
-        &excluded_classes,
-        Some(Regex::new(r#"core_u_object|engine|x21|py_enemy_base|wbp_hud"#).unwrap()),
+        &exclusions.types(),
+        &roots.types(),
+        PackageFilter::new(package_pattern(), None),
     )
     .expect("Failed to generate code");
 
-    let modules: Vec<_> = definitions
+    // Fields missing from the generated structs, for when a layout looks off
+    std::fs::write(out_dir.join("dropped_fields.txt"), code.dropped_fields_report())
+        .expect("Failed to write dropped fields");
+    // Why each type was generated, for when the SDK grows unexpectedly
+    std::fs::write(out_dir.join("inclusions.txt"), code.inclusions_report())
+        .expect("Failed to write inclusions");
+    // Dump entries that were skipped, e.g. after a Dumper-7 update
+    std::fs::write(out_dir.join("diagnostics.txt"), code.diagnostics_report())
+        .expect("Failed to write diagnostics");
+    for diagnostic in &code.diagnostics {
+        println!("cargo::warning=Skipped {}", diagnostic);
+    }
+
+    // Without a package manifest, these types end up in the crate root, where the hand-written code doesn't find them
+    let missing: Vec<_> = CORE_PACKAGES
         .iter()
-        .filter_map(|(package, _)| package.clone())
+        .filter(|it| !code.modules.contains_key(&Some(it.to_string())))
         .collect();
-
-    for (package, mut def) in definitions {
-        match package.clone() {
-            None => {
-                let mut imports = String::new();
-                for module in modules.iter() {
-                    imports.push_str(format!("pub mod {};", module).as_str());
-                }
-
-                def.insert_str(0, imports.as_str());
-            }
-            Some(package) if modified_packages.contains(package.as_str()) => {
-                def.insert_str(0, format!("pub use crate::overrides::{}::*;", package).as_str());
-            }
-            Some(_) => {
-                ()
-            }
-        }
-
-        let module = package.unwrap_or("lib".to_string());
-        let path = output_path.join(format!("{}.rs", module));
-
-        let result = PrettyPlease::default()
-            .format_str(def)
-            .expect(format!("Failed to format code: {}", module).as_str());
-        let mut file = File::create(path).expect("Failed to create output file");
-        write!(file, "{}", result).unwrap();
+    if !missing.is_empty() {
+        panic!("The packages {:?} were not generated, is dump/Manifest.json missing?", missing);
     }
 
+    // Unchanged modules are not rewritten, so rustc can reuse what it compiled from them
+    generator::write_code(&output_path, code.modules, &exclusions.overridden_packages())
+        .expect("Failed to write code");
+
     println!("cargo::rerun-if-changed=build.rs");
+    println!("cargo::rerun-if-changed=exclusions.txt");
+    println!("cargo::rerun-if-changed=roots.txt");
     println!("cargo::rerun-if-changed=dump");
+    println!("cargo::rerun-if-env-changed=MANASDK_DENY_WARNINGS");
+
+    if std::env::var("MANASDK_DENY_WARNINGS").is_ok() && !code.diagnostics.is_empty() {
+        panic!("{} dump entries were skipped, see diagnostics.txt in {}", code.diagnostics.len(), out_dir.display());
+    }
 }
@@ -1,11 +1,12 @@
 use std::cell::LazyCell;
+use std::collections::HashMap;
 use std::collections::hash_set::IntoIter;
 use std::ffi::c_void;
 use std::fmt::{Debug, Formatter};
 use std::mem::ManuallyDrop;
 use std::sync::LazyLock;
-use tracing::info;
-use crate::{BASE_ADDRESS, FNameEntry, UObject, UObjectPointer, offsets};
+use tracing::{info, warn};
+use crate::{allocator, BASE_ADDRESS, FName, FNameEntry, FNameEntryHeader, FString, FWeakObjectPtr, UObject, UObjectPointer, offsets};
 
 #[repr(C)]
 #[derive(Debug, Clone)]
@@ -16,8 +17,9 @@ pub struct FNamePool {
     blocks: [usize; 0x2000],
 }
 
+/// Dropping an array drops its elements and frees its buffer through the [UnrealAllocator](crate::UnrealAllocator).
 #[repr(C)]
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct TArray<T> {
     data: *const T,
     pub num_elements: u32,
@@ -105,7 +107,9 @@ pub union TSparseArrayElementOrFreeListLink<T> {
 
 impl<T> Clone for TSparseArrayElementOrFreeListLink<T> {
     fn clone(&self) -> Self {
-        todo!()
+        // Which variant is active is only known from the allocation flags of the owning array,
+        // so this is a shallow copy of the slot, just like cloning the `TArray` around it
+        unsafe { std::ptr::read(self) }
     }
 }
 
@@ -122,6 +126,17 @@ pub struct InlineAllocator<const NUM_INLINE_ELEMENTS: usize, T> {
     pub secondary_data: *const T,
 }
 
+impl<const NUM_INLINE_ELEMENTS: usize, T> InlineAllocator<NUM_INLINE_ELEMENTS, T> {
+    /// The active allocation, which moves to `secondary_data` once the inline elements are exceeded.
+    pub fn as_ptr(&self) -> *const T {
+        if self.secondary_data.is_null() {
+            self.data.as_ptr()
+        } else {
+            self.secondary_data
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone)]
 pub struct FBitArray {
@@ -130,8 +145,34 @@ pub struct FBitArray {
     pub max_bits: i32,
 }
 
+impl FBitArray {
+    const BITS_PER_WORD: usize = 32;
+
+    pub fn len(&self) -> usize {
+        self.num_bits.max(0) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the bit at `index` is set; bits past the end count as unset.
+    pub fn is_set(&self, index: usize) -> bool {
+        if index >= self.len() {
+            return false;
+        }
+
+        let word = unsafe { *self.data.as_ptr().add(index / Self::BITS_PER_WORD) } as u32;
+        word & (1 << (index % Self::BITS_PER_WORD)) != 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=bool> + '_ {
+        (0..self.len()).map(|index| self.is_set(index))
+    }
+}
+
 #[repr(C)]
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct TSparseArray<T> {
     pub data: TArray<TSparseArrayElementOrFreeListLink<T>>,
     pub allocation_flags: FBitArray,
@@ -139,21 +180,199 @@ pub struct TSparseArray<T> {
     pub num_free_indices: i32,
 }
 
+impl<T> TSparseArray<T> {
+    /// Number of allocated elements, i.e. without the holes of the free list.
+    pub fn len(&self) -> usize {
+        (self.data.num_elements as i32 - self.num_free_indices).max(0) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_allocated(&self, index: usize) -> bool {
+        index < self.data.len() && self.allocation_flags.is_set(index)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if self.is_allocated(index) {
+            Some(unsafe { &self.data[index].element_data })
+        } else {
+            None
+        }
+    }
+
+    /// Iterates the allocated elements together with their index in the sparse array.
+    pub fn iter_indexed(&self) -> TSparseArrayIter<'_, T> {
+        TSparseArrayIter {
+            array: self,
+            index: 0,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=&T> {
+        self.iter_indexed().map(|(_, element)| element)
+    }
+}
+
+impl<T: Debug> Debug for TSparseArray<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+pub struct TSparseArrayIter<'a, T> {
+    array: &'a TSparseArray<T>,
+    index: usize,
+}
+
+impl<'a, T> Iterator for TSparseArrayIter<'a, T> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.array.data.len() {
+            let current = self.index;
+            self.index += 1;
+
+            if let Some(element) = self.array.get(current) {
+                return Some((current, element));
+            }
+        }
+
+        None
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone)]
+pub struct TSetElement<T> {
+    pub value: T,
+    pub hash_next_id: i32,
+    pub hash_index: i32,
+}
+
+#[repr(C)]
+#[derive(Clone)]
 pub struct TSet<T> {
-    pub elements: TSparseArray<T>,
+    pub elements: TSparseArray<TSetElement<T>>,
     pub hash: InlineAllocator<1, i32>,
     pub hash_size: i32,
 }
 
+impl<T> TSet<T> {
+    const INDEX_NONE: i32 = -1;
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=&T> {
+        self.elements.iter().map(|element| &element.value)
+    }
+
+    /// Finds `value` through the hash buckets, like `TSet::Find`.
+    pub fn get(&self, value: &T) -> Option<&T>
+    where
+        T: TypeHash,
+    {
+        self.find_by_hash(value.type_hash(), |it| it.matches(value))
+    }
+
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: TypeHash,
+    {
+        self.get(value).is_some()
+    }
+
+    fn find_by_hash(&self, hash: u32, predicate: impl Fn(&T) -> bool) -> Option<&T> {
+        if self.hash_size <= 0 || self.is_empty() {
+            return None;
+        }
+
+        let bucket = hash as usize & (self.hash_size as usize - 1);
+        let mut id = unsafe { *self.hash.as_ptr().add(bucket) };
+
+        // Bounded by the number of slots, so a corrupted chain can't loop forever
+        for _ in 0..self.elements.data.len() {
+            if id == Self::INDEX_NONE {
+                break;
+            }
+
+            let element = self.elements.get(usize::try_from(id).ok()?)?;
+            if predicate(&element.value) {
+                return Some(&element.value);
+            }
+
+            id = element.hash_next_id;
+        }
+
+        None
+    }
+}
+
+impl<T: Debug> Debug for TSet<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
 
 #[repr(C)]
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct TMap<T1, T2> {
     pub elements: TSet<TPair<T1, T2>>,
 }
 
+impl<T1, T2> TMap<T1, T2> {
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(&T1, &T2)> {
+        self.elements.iter().map(|pair| (&pair.key, &pair.value))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item=&T1> {
+        self.elements.iter().map(|pair| &pair.key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item=&T2> {
+        self.elements.iter().map(|pair| &pair.value)
+    }
+
+    /// Finds the value of `key` through the hash buckets, like `TMap::Find`.
+    pub fn get(&self, key: &T1) -> Option<&T2>
+    where
+        T1: TypeHash,
+    {
+        self.elements
+            .find_by_hash(key.type_hash(), |pair| pair.key.matches(key))
+            .map(|pair| &pair.value)
+    }
+
+    pub fn contains_key(&self, key: &T1) -> bool
+    where
+        T1: TypeHash,
+    {
+        self.get(key).is_some()
+    }
+}
+
+impl<T1: Debug, T2: Debug> Debug for TMap<T1, T2> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone)]
 pub struct TPair<T1, T2> {
@@ -161,6 +380,119 @@ pub struct TPair<T1, T2> {
     pub value: T2,
 }
 
+/// Key hashing of `TSet` and `TMap`, which has to match UE's `GetTypeHash` to find the bucket the engine put a key in.
+pub trait TypeHash {
+    fn type_hash(&self) -> u32;
+
+    /// Key comparison of UE's default key funcs, which is case-insensitive for strings.
+    fn matches(&self, other: &Self) -> bool;
+}
+
+macro_rules! impl_type_hash_32 {
+    ($($ty:ty),*) => {
+        $(impl TypeHash for $ty {
+            fn type_hash(&self) -> u32 {
+                *self as u32
+            }
+
+            fn matches(&self, other: &Self) -> bool {
+                self == other
+            }
+        })*
+    };
+}
+
+impl_type_hash_32!(i8, u8, i16, u16, i32, u32, bool);
+
+macro_rules! impl_type_hash_64 {
+    ($($ty:ty),*) => {
+        $(impl TypeHash for $ty {
+            fn type_hash(&self) -> u32 {
+                (*self as u32).wrapping_add(((*self >> 32) as u32).wrapping_mul(23))
+            }
+
+            fn matches(&self, other: &Self) -> bool {
+                self == other
+            }
+        })*
+    };
+}
+
+impl_type_hash_64!(i64, u64);
+
+impl TypeHash for FName {
+    fn type_hash(&self) -> u32 {
+        (self.comparison_index as u32).wrapping_add(self.number as u32)
+    }
+
+    fn matches(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl<T: AsRef<UObject>> TypeHash for UObjectPointer<T> {
+    fn type_hash(&self) -> u32 {
+        // PointerHash, which ignores the lower 4 bits as they're likely zero anyway
+        ((self.0 as usize as u64) >> 4).type_hash()
+    }
+
+    fn matches(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl TypeHash for FString {
+    /// `FCrc::Strihash_DEPRECATED`, which hashes both bytes of every upper-cased character.
+    fn type_hash(&self) -> u32 {
+        let mut hash = 0u32;
+        for unit in self.data.iter().copied().take_while(|&it| it != 0) {
+            let unit = to_upper_utf16(unit);
+            for byte in [unit & 0xFF, unit >> 8] {
+                hash = (hash >> 8) ^ CRC_TABLE[((hash ^ byte as u32) & 0xFF) as usize];
+            }
+        }
+
+        hash
+    }
+
+    fn matches(&self, other: &Self) -> bool {
+        let units = |string: &Self| {
+            string.data.iter().map(|&it| to_upper_utf16(it)).take_while(|&it| it != 0).collect::<Vec<_>>()
+        };
+
+        units(self) == units(other)
+    }
+}
+
+fn to_upper_utf16(unit: u16) -> u16 {
+    let Some(char) = char::from_u32(unit as u32) else {
+        return unit;
+    };
+
+    let mut upper = char.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(upper), None) if (upper as u32) <= u16::MAX as u32 => upper as u16,
+        _ => unit,
+    }
+}
+
+/// The CRC-32 lookup table of `FCrc::CRCTablesSB8[0]`.
+static CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut crc = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+};
+
 
 #[repr(C)]
 #[derive(Debug, Clone)]
@@ -189,6 +521,15 @@ pub struct TUObjectArray {
 }
 unsafe impl Sync for TUObjectArray {}
 
+impl FUObjectItem {
+    /// `EInternalObjectFlags::Unreachable` and `PendingKill`, which mark objects the garbage collector is about to destroy.
+    const DESTROYED_FLAGS: i32 = (1 << 28) | (1 << 29);
+
+    pub fn is_destroyed(&self) -> bool {
+        self.flags & Self::DESTROYED_FLAGS != 0
+    }
+}
+
 
 static FNAME_POOL: LazyLock<&'static FNamePool> = LazyLock::new(|| {
     let address = *BASE_ADDRESS + offsets::OFFSET_GNAMES;
@@ -226,6 +567,128 @@ impl FNamePool {
             None
         }
     }
+
+    /// Iterates all entries with their index, in the order they were added.
+    pub fn iter(&self) -> FNamePoolIter<'_> {
+        self.iter_from(0)
+    }
+
+    /// Iterates the entries starting at `index`, which has to be the index of an entry or the end of the pool.
+    pub fn iter_from(&self, index: u32) -> FNamePoolIter<'_> {
+        FNamePoolIter {
+            pool: self,
+            block: index >> Self::BLOCK_OFFSET_BITS,
+            offset: (index & (Self::BLOCK_OFFSET - 1)) * Self::ENTRY_STRIDE,
+        }
+    }
+}
+
+pub struct FNamePoolIter<'a> {
+    pool: &'a FNamePool,
+    block: u32,
+    /// Byte offset into the current block
+    offset: u32,
+}
+
+impl FNamePoolIter<'_> {
+    /// The index the next entry will have, once it has been added.
+    pub fn next_index(&self) -> u32 {
+        (self.block << FNamePool::BLOCK_OFFSET_BITS) | (self.offset / FNamePool::ENTRY_STRIDE)
+    }
+}
+
+impl<'a> Iterator for FNamePoolIter<'a> {
+    type Item = (u32, &'a FNameEntry);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let header_size = size_of::<FNameEntryHeader>() as u32;
+
+        while self.block <= self.pool.current_block {
+            let end = if self.block == self.pool.current_block {
+                self.pool.current_byte_cursor
+            } else {
+                FNamePool::BLOCK_OFFSET * FNamePool::ENTRY_STRIDE
+            };
+
+            let block = self.pool.blocks[self.block as usize];
+            // Full blocks are terminated by an empty header
+            let entry = if block != 0 && self.offset + header_size <= end {
+                unsafe { ((block + self.offset as usize) as *const FNameEntry).as_ref() }.filter(|it| it.header.len() > 0)
+            } else {
+                None
+            };
+
+            match entry {
+                Some(entry) => {
+                    let index = self.next_index();
+                    self.offset += entry.size() as u32;
+                    return Some((index, entry));
+                }
+                None if self.block == self.pool.current_block => return None,
+                None => {
+                    self.block += 1;
+                    self.offset = 0;
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Maps the strings of an [FNamePool] to their index, case-insensitively like names are compared.
+///
+/// Entries are only ever added to the pool, so the index catches up with the entries added since the last lookup.
+#[derive(Debug, Default)]
+pub struct FNameIndex {
+    indices: HashMap<String, u32>,
+    next_index: u32,
+}
+
+impl FNameIndex {
+    /// The index of the entry with the string `value`.
+    pub fn find(&mut self, pool: &FNamePool, value: &str) -> Option<u32> {
+        let key = value.to_lowercase();
+        if let Some(index) = self.indices.get(&key) {
+            return Some(*index);
+        }
+
+        let mut entries = pool.iter_from(self.next_index);
+        for (index, entry) in entries.by_ref() {
+            self.indices.entry(entry.get_string().to_string().to_lowercase()).or_insert(index);
+        }
+        self.next_index = entries.next_index();
+
+        self.indices.get(&key).copied()
+    }
+
+    /// Resolves `value` to a name, splitting a number suffix off it like the engine does.
+    pub fn find_name(&mut self, pool: &FNamePool, value: &str) -> Option<FName> {
+        let (base, number) = split_number(value);
+        if let Some(index) = self.find(pool, base) {
+            return Some(FName { comparison_index: index as i32, number });
+        }
+
+        if number != 0 {
+            // The engine registers the whole string if the split off base has never been added
+            return self.find(pool, value).map(|index| FName { comparison_index: index as i32, number: 0 });
+        }
+
+        None
+    }
+}
+
+/// Splits `Name_12` into `Name` and the internal number 13, like `FName` does on construction.
+fn split_number(value: &str) -> (&str, i32) {
+    let digits = value.bytes().rev().take_while(u8::is_ascii_digit).count();
+    let (base, suffix) = value.split_at(value.len() - digits);
+
+    // Leading zeros would be lost, so they aren't split off
+    let has_leading_zero = suffix.len() > 1 && suffix.starts_with('0');
+    match (base.strip_suffix('_'), suffix.parse::<i32>()) {
+        (Some(base), Ok(number)) if !base.is_empty() && !has_leading_zero && number < i32::MAX => (base, number + 1),
+        _ => (value, 0),
+    }
 }
 
 
@@ -245,6 +708,11 @@ impl TUObjectArray {
     }
 
     pub fn get_by_index(&self, index: usize) -> Option<&UObject> {
+        self.get_item_by_index(index)?.object.as_ref()
+    }
+
+    /// The slot of the object at `index`, which also holds its serial number for weak pointers.
+    pub fn get_item_by_index(&self, index: usize) -> Option<&FUObjectItem> {
         let chunk_index = index / Self::ELEMENTS_PER_CHUNK;
         let in_chunk_index = index % Self::ELEMENTS_PER_CHUNK;
 
@@ -253,9 +721,39 @@ impl TUObjectArray {
         }
 
         let chunk = unsafe { self.objects.add(chunk_index).as_ref() }?.clone();
-        let object = &unsafe { chunk.add(in_chunk_index).as_ref() }?.object;
+        unsafe { chunk.add(in_chunk_index).as_ref() }
+    }
+
+    /// Resolves `pointer` if its slot still holds the object it was created for, like `FWeakObjectPtr::Get`.
+    ///
+    /// Slots are reused for new objects, which get a new serial number, so a matching serial number means the object is
+    /// still alive. Objects that are about to be garbage collected resolve to `None`.
+    pub fn get_weak(&self, pointer: &FWeakObjectPtr) -> Option<&UObject> {
+        if pointer.object_serial_number == 0 {
+            return None;
+        }
+
+        let item = self.get_item_by_index(usize::try_from(pointer.object_index).ok()?)?;
+        if item.serial_number != pointer.object_serial_number || item.is_destroyed() {
+            return None;
+        }
+
+        item.object.as_ref()
+    }
+
+    /// Weakly references `object`, if the engine has assigned it a serial number.
+    ///
+    /// The engine assigns serial numbers on demand when it creates the first weak pointer to an object.
+    pub fn weak_ptr(&self, object: &UObject) -> Option<FWeakObjectPtr> {
+        let item = self.get_item_by_index(usize::try_from(object.index).ok()?)?;
+        if item.serial_number == 0 || !std::ptr::eq(item.object.0, object) {
+            return None;
+        }
 
-        object.as_ref()
+        Some(FWeakObjectPtr {
+            object_index: object.index,
+            object_serial_number: item.serial_number,
+        })
     }
 
     pub fn iter(&self) -> impl Iterator<Item=&UObject> {
@@ -324,6 +822,168 @@ impl<T> TArray<T> {
     pub fn len(&self) -> usize {
         self.num_elements as usize
     }
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut array = Self::new();
+        array.reserve(capacity);
+        array
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.max_elements as usize
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        if self.data.is_null() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.data, self.len()) }
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        if self.data.is_null() {
+            &mut []
+        } else {
+            unsafe { std::slice::from_raw_parts_mut(self.data as *mut T, self.len()) }
+        }
+    }
+
+    /// Grows the buffer through the [UnrealAllocator](crate::UnrealAllocator) so it fits `additional` more elements.
+    ///
+    /// # Panics
+    /// If no allocator has been set up, or the allocation failed.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len() + additional;
+        if required <= self.capacity() {
+            return;
+        }
+
+        let capacity = required.max(self.capacity() * 2).max(4);
+        let size = capacity.checked_mul(size_of::<T>().max(1)).expect("TArray capacity overflow");
+        let allocator = allocator().expect("No allocator has been set up, see manasdk::set_allocator");
+        let data = unsafe { allocator.realloc(self.data as *mut c_void, size, align_of::<T>()) };
+        assert!(!data.is_null(), "Unable to allocate {} bytes for a TArray", size);
+
+        self.data = data as *const T;
+        self.max_elements = u32::try_from(capacity).expect("TArray capacity overflow");
+    }
+
+    /// Appends `value`, growing the buffer through the [UnrealAllocator](crate::UnrealAllocator) if needed.
+    pub fn push(&mut self, value: T) {
+        self.reserve(1);
+        if self.push_within_capacity(value).is_err() {
+            unreachable!("TArray has room after reserving");
+        }
+    }
+
+    /// Removes the element at `index` and shifts the following ones down, like UE's `RemoveAt`.
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.len(), "Index {} out of bounds ({})", index, self.len());
+
+        let data = self.data as *mut T;
+        let trailing = self.len() - index - 1;
+        self.num_elements -= 1;
+        unsafe {
+            let value = data.add(index).read();
+            std::ptr::copy(data.add(index + 1), data.add(index), trailing);
+            value
+        }
+    }
+
+    /// Drops all elements, but keeps the buffer.
+    pub fn clear(&mut self) {
+        let elements = self.as_mut_slice() as *mut [T];
+        self.num_elements = 0;
+        unsafe { std::ptr::drop_in_place(elements) };
+    }
+
+    /// Wraps memory that is owned elsewhere, usually by the engine.
+    ///
+    /// # Safety
+    /// `data` must point to `max_elements` slots, of which the first `num_elements` are initialized.
+    /// As the array frees `data` through the [UnrealAllocator](crate::UnrealAllocator) when dropped, memory of another origin
+    /// has to be wrapped in [ManuallyDrop].
+    pub unsafe fn from_raw_parts(data: *mut T, num_elements: u32, max_elements: u32) -> Self {
+        Self {
+            data,
+            num_elements,
+            max_elements,
+        }
+    }
+
+    /// Appends `value` if the current allocation has room for it, which needs no allocator.
+    pub fn push_within_capacity(&mut self, value: T) -> Result<(), T> {
+        if self.data.is_null() || self.num_elements >= self.max_elements {
+            return Err(value);
+        }
+
+        unsafe { (self.data as *mut T).add(self.len()).write(value) };
+        self.num_elements += 1;
+        Ok(())
+    }
+
+    /// Removes the element at `index` by moving the last element into its place, like UE's `RemoveAtSwap`.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        assert!(index < self.len(), "Index {} out of bounds ({})", index, self.len());
+
+        let data = self.data as *mut T;
+        let last = self.len() - 1;
+        self.num_elements -= 1;
+        unsafe {
+            let value = data.add(index).read();
+            if index != last {
+                data.add(index).write(data.add(last).read());
+            }
+            value
+        }
+    }
+}
+
+impl<T> Extend<T> for TArray<T> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for TArray<T> {
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let mut array = Self::new();
+        array.extend(iter);
+        array
+    }
+}
+
+impl<T: Clone> Clone for TArray<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Drop for TArray<T> {
+    fn drop(&mut self) {
+        if self.data.is_null() {
+            return;
+        }
+
+        self.clear();
+        match allocator() {
+            Some(allocator) => unsafe { allocator.free(self.data as *mut c_void) },
+            None => warn!("No allocator has been set up, leaking a TArray of {} elements", self.max_elements),
+        }
+    }
 }
 
 impl<'a, T> Iterator for TArrayIter<'a, T> {
@@ -339,3 +999,380 @@ impl<'a, T> Iterator for TArrayIter<'a, T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Backing memory of a synthetic [TSet], laid out the way the engine would fill it in.
+    struct SetStorage<T> {
+        slots: Vec<TSparseArrayElementOrFreeListLink<TSetElement<T>>>,
+        words: Vec<i32>,
+        buckets: Vec<i32>,
+    }
+
+    impl<T> SetStorage<T> {
+        /// `None` entries become holes of the free list.
+        fn new(values: Vec<Option<T>>, hash_size: usize, hash: impl Fn(&T) -> u32) -> Self {
+            let mut words = vec![0; values.len().div_ceil(32).max(4)];
+            let mut buckets = vec![-1; hash_size];
+            let slots = values
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| match value {
+                    Some(value) => {
+                        words[index / 32] |= 1 << (index % 32);
+                        let bucket = hash(&value) as usize & (hash_size - 1);
+                        let element = TSetElement {
+                            value,
+                            hash_next_id: buckets[bucket],
+                            hash_index: bucket as i32,
+                        };
+                        buckets[bucket] = index as i32;
+                        TSparseArrayElementOrFreeListLink { element_data: ManuallyDrop::new(element) }
+                    }
+                    None => TSparseArrayElementOrFreeListLink { prev_next_free_index: (-1, -1) },
+                })
+                .collect();
+
+            Self { slots, words, buckets }
+        }
+
+        /// The set borrows the storage, so it must not free it.
+        fn set(&mut self) -> ManuallyDrop<TSet<T>> {
+            let num_bits = self.slots.len() as i32;
+            let num_free = (0..self.slots.len()).filter(|&it| self.words[it / 32] & (1 << (it % 32)) == 0).count();
+            let len = self.slots.len() as u32;
+
+            // Small sets live in the inline storage, bigger ones in the secondary allocation
+            let allocation_flags = if self.words.len() <= 4 {
+                FBitArray {
+                    data: InlineAllocator { data: self.words[..4].try_into().unwrap(), secondary_data: std::ptr::null() },
+                    num_bits,
+                    max_bits: 128,
+                }
+            } else {
+                FBitArray {
+                    data: InlineAllocator { data: [0; 4], secondary_data: self.words.as_ptr() },
+                    num_bits,
+                    max_bits: self.words.len() as i32 * 32,
+                }
+            };
+
+            let hash = if self.buckets.len() == 1 {
+                InlineAllocator { data: [self.buckets[0]], secondary_data: std::ptr::null() }
+            } else {
+                InlineAllocator { data: [-1], secondary_data: self.buckets.as_ptr() }
+            };
+
+            ManuallyDrop::new(TSet {
+                elements: TSparseArray {
+                    data: unsafe { TArray::from_raw_parts(self.slots.as_mut_ptr(), len, len) },
+                    allocation_flags,
+                    first_free_index: -1,
+                    num_free_indices: num_free as i32,
+                },
+                hash,
+                hash_size: self.buckets.len() as i32,
+            })
+        }
+    }
+
+    fn pair<T1, T2>(key: T1, value: T2) -> Option<TPair<T1, T2>> {
+        Some(TPair { key, value })
+    }
+
+    fn key_hash<T1: TypeHash, T2>(pair: &TPair<T1, T2>) -> u32 {
+        pair.key.type_hash()
+    }
+
+    fn map<T1, T2>(storage: &mut SetStorage<TPair<T1, T2>>) -> ManuallyDrop<TMap<T1, T2>> {
+        ManuallyDrop::new(TMap { elements: ManuallyDrop::into_inner(storage.set()) })
+    }
+
+    #[test]
+    fn test_sparse_array_skips_holes() {
+        let mut storage = SetStorage::new(vec![Some(1), None, Some(3), None, Some(5)], 4, i32::type_hash);
+        let set = storage.set();
+
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.elements.iter_indexed().map(|(index, it)| (index, it.value)).collect::<Vec<_>>(), vec![(0, 1), (2, 3), (4, 5)]);
+        assert!(set.elements.get(1).is_none());
+        assert!(set.elements.get(5).is_none());
+        assert_eq!(format!("{:?}", *set), "{1, 3, 5}");
+    }
+
+    #[test]
+    fn test_bit_array_secondary_storage() {
+        let values = (0..200).map(|it| if it % 3 == 0 { Some(it) } else { None }).collect();
+        let mut storage = SetStorage::new(values, 64, i32::type_hash);
+        let set = storage.set();
+
+        assert!(!set.elements.allocation_flags.data.secondary_data.is_null());
+        assert_eq!(set.elements.allocation_flags.iter().filter(|&it| it).count(), 67);
+        assert_eq!(set.iter().copied().collect::<Vec<_>>(), (0..200).step_by(3).collect::<Vec<_>>());
+        assert!(set.contains(&198));
+        assert!(!set.contains(&199));
+    }
+
+    #[test]
+    fn test_map_lookup() {
+        let mut storage = SetStorage::new(vec![pair(10, "ten"), None, pair(2, "two"), pair(18, "eighteen")], 8, key_hash);
+        let map = map(&mut storage);
+
+        // All keys land in bucket 2, so finding them follows the hash chain
+        assert_eq!(map.get(&10), Some(&"ten"));
+        assert_eq!(map.get(&18), Some(&"eighteen"));
+        assert_eq!(map.get(&2), Some(&"two"));
+        assert_eq!(map.get(&26), None);
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![10, 2, 18]);
+        assert_eq!(format!("{:?}", *map), r#"{10: "ten", 2: "two", 18: "eighteen"}"#);
+    }
+
+    #[test]
+    fn test_map_inline_hash() {
+        let mut storage = SetStorage::new(vec![pair(1u64 << 40, 1), pair(7u64, 2)], 1, key_hash);
+        let map = map(&mut storage);
+
+        assert_eq!(map.get(&(1u64 << 40)), Some(&1));
+        assert_eq!(map.get(&7), Some(&2));
+        assert_eq!(map.get(&8), None);
+    }
+
+    #[test]
+    fn test_empty_map() {
+        let mut storage = SetStorage::<TPair<i32, i32>>::new(vec![], 1, key_hash);
+        let mut map = map(&mut storage);
+        map.elements.hash_size = 0;
+
+        assert!(map.is_empty());
+        assert_eq!(map.get(&0), None);
+        assert_eq!(format!("{:?}", *map), "{}");
+    }
+
+    #[test]
+    fn test_f_string_keys() {
+        let mut storage = SetStorage::new(vec![pair(FString::from("Hero_Val"), 1), pair(FString::from("Hero_Julei"), 2)], 4, key_hash);
+        let map = map(&mut storage);
+
+        // String keys compare case-insensitively, so they hash case-insensitively too
+        assert_eq!(map.get(&FString::from("hero_val")), Some(&1));
+        assert_eq!(map.get(&FString::from("Hero")), None);
+    }
+
+    #[test]
+    fn test_type_hash() {
+        assert_eq!(0x1_0000_0002u64.type_hash(), 2 + 23);
+        assert_eq!((-1i8).type_hash(), u32::MAX);
+        assert_eq!(FName { comparison_index: 5, number: 2 }.type_hash(), 7);
+    }
+
+    #[test]
+    fn test_array_growth() {
+        let mut array = TArray::new();
+        array.push(1);
+        array.extend(2..=10);
+        assert_eq!(array.len(), 10);
+        assert!(array.capacity() >= 10);
+
+        assert_eq!(array.remove(0), 1);
+        assert_eq!(array.remove(8), 10);
+        assert_eq!(array.as_slice(), &[2, 3, 4, 5, 6, 7, 8, 9]);
+
+        let copy = array.clone();
+        array.clear();
+        assert!(array.is_empty());
+        assert_eq!(copy.iter().sum::<i32>(), 44);
+    }
+
+    #[test]
+    fn test_array_drops_elements() {
+        let counter = std::rc::Rc::new(());
+        let mut array = (0..5).map(|_| counter.clone()).collect::<TArray<_>>();
+        assert_eq!(std::rc::Rc::strong_count(&counter), 6);
+
+        drop(array.remove(2));
+        assert_eq!(std::rc::Rc::strong_count(&counter), 5);
+
+        drop(array);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+    }
+
+    /// Blocks of a synthetic [FNamePool], filled like the engine's entry allocator does.
+    struct PoolStorage {
+        pool: Box<FNamePool>,
+        blocks: Vec<Vec<u16>>,
+    }
+
+    impl PoolStorage {
+        fn new() -> Self {
+            let mut storage = Self {
+                pool: Box::new(FNamePool {
+                    _padding: [0; 8],
+                    current_block: 0,
+                    current_byte_cursor: 0,
+                    blocks: [0; 0x2000],
+                }),
+                blocks: vec![],
+            };
+            storage.allocate_block();
+            storage
+        }
+
+        /// Leaves the rest of the current block empty, which terminates it.
+        fn allocate_block(&mut self) {
+            // Entries are read as a whole FNameEntry, so they may reach past the block
+            let block = vec![0u16; 0x10000 + size_of::<FNameEntry>() / 2];
+            self.pool.current_block = self.blocks.len() as u32;
+            self.pool.current_byte_cursor = 0;
+            self.pool.blocks[self.blocks.len()] = block.as_ptr() as usize;
+            self.blocks.push(block);
+        }
+
+        fn add(&mut self, name: &str) -> u32 {
+            let (header, units) = if name.is_ascii() {
+                let units = name.as_bytes().chunks(2).map(|it| u16::from_le_bytes([it[0], *it.get(1).unwrap_or(&0)]));
+                ((name.len() as u16) << 6, units.collect::<Vec<_>>())
+            } else {
+                let units = name.encode_utf16().collect::<Vec<_>>();
+                (((units.len() as u16) << 6) | 1, units)
+            };
+
+            let offset = self.pool.current_byte_cursor as usize / 2;
+            let block = self.blocks.last_mut().unwrap();
+            block[offset] = header;
+            block[offset + 1..offset + 1 + units.len()].copy_from_slice(&units);
+            self.pool.current_byte_cursor += 2 * (1 + units.len() as u32);
+
+            (self.pool.current_block << 16) | offset as u32
+        }
+    }
+
+    #[test]
+    fn test_name_pool_iter() {
+        let mut storage = PoolStorage::new();
+        let none = storage.add("None");
+        let odd = storage.add("Odd");
+        storage.allocate_block();
+        let wide = storage.add("マナ");
+        let hero = storage.add("Hero");
+        assert_eq!((none, odd, wide, hero), (0, 3, 0x10000, 0x10003));
+
+        let entries = storage.pool.iter().map(|(index, it)| (index, it.get_string().to_string())).collect::<Vec<_>>();
+        assert_eq!(entries, vec![
+            (none, "None".to_string()),
+            (odd, "Odd".to_string()),
+            (wide, "マナ".to_string()),
+            (hero, "Hero".to_string()),
+        ]);
+
+        for (index, string) in entries {
+            assert_eq!(storage.pool.entry_by_index(index).unwrap().get_string().to_string(), string);
+        }
+        assert_eq!(storage.pool.iter_from(wide).count(), 2);
+    }
+
+    #[test]
+    fn test_name_index() {
+        let mut storage = PoolStorage::new();
+        storage.add("None");
+        let hero = storage.add("Hero");
+
+        let mut index = FNameIndex::default();
+        assert_eq!(index.find(&storage.pool, "hero"), Some(hero));
+        assert_eq!(index.find(&storage.pool, "Mana"), None);
+
+        // Names registered later are picked up on the next miss
+        storage.allocate_block();
+        let mana = storage.add("Mana");
+        assert_eq!(index.find(&storage.pool, "MANA"), Some(mana));
+        assert_eq!(index.find(&storage.pool, "None"), Some(0));
+    }
+
+    #[test]
+    fn test_find_name() {
+        let mut storage = PoolStorage::new();
+        let hero = storage.add("Hero");
+        let version = storage.add("Version_2");
+        let padded = storage.add("Hero_02");
+
+        let mut index = FNameIndex::default();
+        let name = |comparison_index, number| Some(FName { comparison_index: comparison_index as i32, number });
+        assert_eq!(index.find_name(&storage.pool, "Hero"), name(hero, 0));
+        assert_eq!(index.find_name(&storage.pool, "Hero_0"), name(hero, 1));
+        assert_eq!(index.find_name(&storage.pool, "hero_12"), name(hero, 13));
+        assert_eq!(index.find_name(&storage.pool, "Version_2"), name(version, 0));
+        assert_eq!(index.find_name(&storage.pool, "Hero_02"), name(padded, 0));
+        assert_eq!(index.find_name(&storage.pool, "Heroine_1"), None);
+    }
+
+    #[test]
+    fn test_split_number() {
+        assert_eq!(split_number("Name_12"), ("Name", 13));
+        assert_eq!(split_number("Name_0"), ("Name", 1));
+        assert_eq!(split_number("Name_012"), ("Name_012", 0));
+        assert_eq!(split_number("Name12"), ("Name12", 0));
+        assert_eq!(split_number("_12"), ("_12", 0));
+        assert_eq!(split_number("Name_"), ("Name_", 0));
+        assert_eq!(split_number("Name_99999999999"), ("Name_99999999999", 0));
+    }
+
+    fn object(index: i32) -> UObject {
+        UObject {
+            v_table: std::ptr::null(),
+            flags: Default::default(),
+            index,
+            class: Default::default(),
+            name: FName { comparison_index: 0, number: 0 },
+            outer: Default::default(),
+        }
+    }
+
+    /// Runs `f` on an object array with a single chunk holding `items`.
+    fn with_objects<R>(items: &[FUObjectItem], f: impl FnOnce(&TUObjectArray) -> R) -> R {
+        let chunks = [items.as_ptr()];
+        f(&TUObjectArray {
+            objects: chunks.as_ptr(),
+            pre_allocated_objects: std::ptr::null(),
+            max_elements: items.len() as i32,
+            num_elements: items.len() as i32,
+            max_chunks: 1,
+            num_chunks: 1,
+        })
+    }
+
+    #[test]
+    fn test_weak_pointers() {
+        let objects = [object(0), object(1)];
+        let mut items = objects
+            .iter()
+            .map(|it| FUObjectItem { object: it.into(), flags: 0, cluster_root_index: 0, serial_number: 0 })
+            .collect::<Vec<_>>();
+        items[1].serial_number = 7;
+
+        let pointer = with_objects(&items, |array| {
+            // The engine hasn't weakly referenced the first object yet
+            assert_eq!(array.weak_ptr(&objects[0]), None);
+            assert_eq!(array.weak_ptr(&object(1)), None);
+            array.weak_ptr(&objects[1])
+        });
+        assert_eq!(pointer, Some(FWeakObjectPtr { object_index: 1, object_serial_number: 7 }));
+
+        let resolve = |items: &[FUObjectItem], pointer: FWeakObjectPtr| {
+            with_objects(items, |array| array.get_weak(&pointer).map(|it| it as *const UObject))
+        };
+        let pointer = pointer.unwrap();
+        assert_eq!(resolve(&items, pointer.clone()), Some(&objects[1] as *const UObject));
+        assert_eq!(resolve(&items, FWeakObjectPtr { object_index: 2, object_serial_number: 7 }), None);
+        assert_eq!(resolve(&items, FWeakObjectPtr { object_index: 0, object_serial_number: 0 }), None);
+
+        // Pending kill
+        items[1].flags = 1 << 29;
+        assert_eq!(resolve(&items, pointer.clone()), None);
+
+        // Another object took over the slot
+        items[1].flags = 0;
+        items[1].serial_number = 8;
+        assert_eq!(resolve(&items, pointer), None);
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+use crate::core_u_object::UFunction;
+use crate::{FName, FWeakObjectPtr, FunctionParams, SdkError, TArray, UObject};
+
+/// A single `UObject` + function binding, which the engine invokes through `ProcessEvent` by name.
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FScriptDelegate {
+    pub object: FWeakObjectPtr,
+    pub function_name: FName,
+}
+
+/// Single-cast delegates are a bare binding.
+pub type FDelegateProperty_ = FScriptDelegate;
+
+impl FScriptDelegate {
+    /// Binds `function` on `object`, which must be a function of the object's class.
+    pub fn new(object: &UObject, function: &UFunction) -> Result<Self, SdkError> {
+        let class = object
+            .class
+            .as_ref()
+            .ok_or(SdkError::NotBindable("the object has no class"))?;
+        if class.find_function_by_name(&function.name).is_none() {
+            return Err(SdkError::NotBindable("the function is not part of the object's class"));
+        }
+
+        Ok(Self {
+            object: FWeakObjectPtr::new(object)
+                .ok_or(SdkError::NotBindable("the object has no serial number yet"))?,
+            function_name: function.name.clone(),
+        })
+    }
+}
+
+/// A multicast delegate whose invocation list lives inline, i.e. `FMulticastInlineDelegate`.
+///
+/// `P` is the params struct of the delegate's signature function, or `()` when the generator could not find it.
+/// The engine copies the invocation list before broadcasting, but it is not synchronized,
+/// so it must only be modified on the game thread.
+#[repr(C)]
+pub struct TMulticastInlineDelegate<P = ()> {
+    pub invocation_list: TArray<FScriptDelegate>,
+    _signature: PhantomData<fn(P)>,
+}
+
+pub type FMulticastInlineDelegate = TMulticastInlineDelegate;
+pub type FMulticastInlineDelegateProperty_ = TMulticastInlineDelegate;
+
+impl<P> TMulticastInlineDelegate<P> {
+    pub fn bindings(&self) -> impl Iterator<Item = &FScriptDelegate> {
+        self.invocation_list.iter()
+    }
+
+    pub fn is_bound(&self) -> bool {
+        self.invocation_list.len() > 0
+    }
+
+    pub fn contains(&self, binding: &FScriptDelegate) -> bool {
+        self.bindings().any(|it| it == binding)
+    }
+
+    /// Adds `binding` unless it is already bound.
+    ///
+    /// The invocation list is owned by the engine, so this fails when its allocation has no room left.
+    ///
+    /// # Safety
+    /// The function of `binding` must take the parameters of the delegate's signature.
+    pub unsafe fn add_binding(&mut self, binding: FScriptDelegate) -> Result<(), SdkError> {
+        if self.contains(&binding) {
+            return Ok(());
+        }
+
+        let capacity = self.invocation_list.max_elements as usize;
+        self.invocation_list
+            .push_within_capacity(binding)
+            .map_err(|_| SdkError::DelegateFull { capacity })
+    }
+
+    /// Removes `binding`, returning whether it was bound.
+    pub fn remove_binding(&mut self, binding: &FScriptDelegate) -> bool {
+        let index = self.bindings().position(|it| it == binding);
+        if let Some(index) = index {
+            self.invocation_list.swap_remove(index);
+        }
+
+        index.is_some()
+    }
+
+    pub fn remove(&mut self, object: &UObject, function: &UFunction) -> bool {
+        FScriptDelegate::new(object, function)
+            .map(|binding| self.remove_binding(&binding))
+            .unwrap_or_default()
+    }
+}
+
+impl<P: FunctionParams> TMulticastInlineDelegate<P> {
+    /// Binds `function` on `object`, after checking that it takes the parameters of the signature.
+    pub fn add(&mut self, object: &UObject, function: &UFunction) -> Result<(), SdkError> {
+        P::check(function)?;
+        let binding = FScriptDelegate::new(object, function)?;
+
+        unsafe { self.add_binding(binding) }
+    }
+}
+
+impl<P> Clone for TMulticastInlineDelegate<P> {
+    fn clone(&self) -> Self {
+        Self {
+            invocation_list: self.invocation_list.clone(),
+            _signature: PhantomData,
+        }
+    }
+}
+
+impl<P> Debug for TMulticastInlineDelegate<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.bindings()).finish()
+    }
+}
+
+/// A multicast delegate that only stores whether it is bound, i.e. `FSparseDelegate`.
+///
+/// The invocation lists of sparse delegates live in the engine's global `FSparseDelegateStorage`,
+/// which the dump does not locate, so they can only be inspected through [Self::is_bound].
+#[repr(C)]
+pub struct TMulticastSparseDelegate<P = ()> {
+    pub b_is_bound: bool,
+    _signature: PhantomData<fn(P)>,
+}
+
+pub type FMulticastSparseDelegateProperty_ = TMulticastSparseDelegate;
+
+impl<P> TMulticastSparseDelegate<P> {
+    pub fn is_bound(&self) -> bool {
+        self.b_is_bound
+    }
+}
+
+impl<P> Clone for TMulticastSparseDelegate<P> {
+    fn clone(&self) -> Self {
+        Self {
+            b_is_bound: self.b_is_bound,
+            _signature: PhantomData,
+        }
+    }
+}
+
+impl<P> Debug for TMulticastSparseDelegate<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TMulticastSparseDelegate")
+            .field("b_is_bound", &self.b_is_bound)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+    use super::*;
+
+    fn binding(object_index: i32) -> FScriptDelegate {
+        FScriptDelegate {
+            object: FWeakObjectPtr {
+                object_index,
+                object_serial_number: 1,
+            },
+            function_name: FName {
+                comparison_index: 42,
+                number: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_delegate_layout() {
+        assert_eq!(size_of::<FScriptDelegate>(), 0x10);
+        assert_eq!(size_of::<TMulticastInlineDelegate<()>>(), 0x10);
+        assert_eq!(size_of::<TMulticastSparseDelegate<()>>(), 0x1);
+    }
+
+    #[test]
+    fn test_invocation_list() {
+        let mut delegate = FMulticastInlineDelegate {
+            invocation_list: TArray::with_capacity(2),
+            _signature: PhantomData,
+        };
+        assert!(!delegate.is_bound());
+
+        unsafe {
+            delegate.add_binding(binding(1)).unwrap();
+            delegate.add_binding(binding(1)).unwrap();
+            delegate.add_binding(binding(2)).unwrap();
+            assert_eq!(delegate.add_binding(binding(3)), Err(SdkError::DelegateFull { capacity: 2 }));
+        }
+        assert!(delegate.contains(&binding(2)));
+        assert_eq!(delegate.bindings().count(), 2);
+
+        assert!(delegate.remove_binding(&binding(1)));
+        assert!(!delegate.remove_binding(&binding(1)));
+        assert_eq!(delegate.bindings().collect::<Vec<_>>(), vec![&binding(2)]);
+    }
+}
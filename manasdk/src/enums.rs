@@ -1,4 +1,5 @@
 use flagset::flags;
+use std::fmt::{Display, Formatter};
 
 flags! {
     // EObjectFlags equivalent in Rust
@@ -239,4 +240,33 @@ flags! {
         SkipSerialization = 0x0080000000000000,
     }
 }
-    
\ No newline at end of file
+    
+/// Error returned when converting an integer that is no variant of a generated enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownEnumValue {
+    pub enum_name: &'static str,
+    pub value: u64,
+}
+
+impl Display for UnknownEnumValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown value for {}: {}", self.enum_name, self.value)
+    }
+}
+
+impl std::error::Error for UnknownEnumValue {}
+
+/// Error returned when parsing a name that is no variant of a generated enum.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownEnumName {
+    pub enum_name: &'static str,
+    pub name: String,
+}
+
+impl Display for UnknownEnumName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown name for {}: {}", self.enum_name, self.name)
+    }
+}
+
+impl std::error::Error for UnknownEnumName {}
@@ -1,5 +1,5 @@
 use crate::core_u_object::{UClass, UEnum, UFunction, UObject, UStruct};
-use crate::{EClassCastFlags, EClassFlags, FName, TArray, TWeakObjectPtr, UObjectPointer};
+use crate::{EClassCastFlags, EClassFlags, FName, FProperty, TArray, TWeakObjectPtr, UObjectPointer};
 use flagset::FlagSet;
 use manasdk_macros::extend;
 use std::fmt::{Debug, Formatter};
@@ -62,18 +62,6 @@ pub struct FField {
     pub obj_flags: i32,
 }
 
-#[repr(C)]
-#[extend(FField)]
-#[derive(Debug, Clone)]
-pub struct FProperty {
-    pub array_dim: i32,
-    pub element_size: i32,
-    pub property_flags: u64,
-    pub pad_48: [u8; 4],
-    pub offset: i32,
-    pub pad_50: [u8; 40],
-}
-
 #[repr(C)]
 #[extend(FProperty)]
 #[derive(Debug, Clone)]
@@ -116,7 +104,7 @@ pub struct FStructProperty {
 #[extend(FProperty)]
 #[derive(Debug, Clone)]
 pub struct FArrayProperty {
-    pub inner_property: *mut crate::FProperty,
+    pub inner_property: *mut FProperty,
 }
 
 #[repr(C)]
@@ -130,22 +118,22 @@ pub struct FDelegateProperty {
 #[extend(FProperty)]
 #[derive(Debug, Clone)]
 pub struct FMapProperty {
-    pub key_property: *mut crate::FProperty,
-    pub value_property: *mut crate::FProperty,
+    pub key_property: *mut FProperty,
+    pub value_property: *mut FProperty,
 }
 
 #[repr(C)]
 #[extend(FProperty)]
 #[derive(Debug, Clone)]
 pub struct FSetProperty {
-    pub element_property: *mut crate::FProperty,
+    pub element_property: *mut FProperty,
 }
 
 #[repr(C)]
 #[extend(FProperty)]
 #[derive(Debug, Clone)]
 pub struct FEnumProperty {
-    pub underlying_property: *mut crate::FProperty,
+    pub underlying_property: *mut FProperty,
     pub enum_: *mut UEnum,
 }
 
@@ -160,7 +148,7 @@ pub struct FFieldPathProperty {
 #[extend(FProperty)]
 #[derive(Debug, Clone)]
 pub struct FOptionalProperty {
-    pub value_property: *mut crate::FProperty,
+    pub value_property: *mut FProperty,
 }
 
 #[repr(C)]
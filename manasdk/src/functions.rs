@@ -1,10 +1,12 @@
 use std::ffi::c_void;
 use std::fmt::{Display, Formatter};
 use std::iter::once;
+use std::marker::PhantomData;
+use std::ptr::null_mut;
 use std::sync::LazyLock;
 
-use crate::core_u_object::{UField, UFunction, UStruct};
-use crate::{offsets, EClassCastFlags, EObjectFlags, FName, FProperty, HasClassObject, TUObjectArray, UClass, UObject, UObjectPointer, BASE_ADDRESS};
+use crate::core_u_object::{FSoftObjectPath, UField, UFunction, UStruct};
+use crate::{offsets, EClassCastFlags, EObjectFlags, EPropertyFlags, FFrame, FName, FWeakObjectPtr, FOutParmRec, FProperty, HasClassObject, IsA, TLazyObjectPtr, TSoftClassPtr, TSoftObjectPtr, TUObjectArray, TWeakObjectPtr, UClass, UObject, UObjectPointer, BASE_ADDRESS};
 use dashmap::DashMap;
 use flagset::FlagSet;
 
@@ -26,6 +28,86 @@ impl Display for PointerError {
 
 impl std::error::Error for PointerError {}
 
+/// Error returned by the `try_` variants of generated function wrappers when the
+/// reflection data they rely on cannot be found at runtime (e.g. after a game update).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SdkError {
+    ClassNotFound(&'static str),
+    FunctionNotFound {
+        class: &'static str,
+        function: &'static str,
+    },
+    NoDefaultObject(&'static str),
+    /// The size of the generated params struct differs from the `parms_size` of the [UFunction].
+    ParamsMismatch {
+        class: &'static str,
+        function: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// The exec function of the [UFunction] is not where the dump put it.
+    NativeMismatch {
+        class: &'static str,
+        function: &'static str,
+    },
+    /// The object and function cannot be bound to a delegate, for the given reason.
+    NotBindable(&'static str),
+    /// The engine-owned invocation list of a delegate has no room for another binding.
+    DelegateFull { capacity: usize },
+}
+
+impl Display for SdkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SdkError::ClassNotFound(class) => write!(f, "Unable to find {}", class),
+            SdkError::FunctionNotFound { class, function } => {
+                write!(f, "Unable to find {}::{}", class, function)
+            }
+            SdkError::NoDefaultObject(class) => write!(f, "No default object for {}", class),
+            SdkError::ParamsMismatch { class, function, expected, actual } => write!(
+                f,
+                "Params of {}::{} take {} bytes, but the function expects {}",
+                class, function, expected, actual
+            ),
+            SdkError::NativeMismatch { class, function } => {
+                write!(f, "Exec function of {}::{} is not at the dumped offset", class, function)
+            }
+            SdkError::NotBindable(reason) => write!(f, "Unable to bind delegate: {}", reason),
+            SdkError::DelegateFull { capacity } => {
+                write!(f, "Invocation list is full ({} bindings)", capacity)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SdkError {}
+
+/// The parameters of a [UFunction], as generated into the `params` module of each package.
+///
+/// The fields are laid out like the `FProperty` children of the function, followed by the return value.
+pub trait FunctionParams {
+    /// Name of the owning class, without prefix.
+    const CLASS: &'static str;
+    const FUNCTION: &'static str;
+    /// The offset right after the last parameter, which is what [UFunction::parms_size] holds.
+    const PARMS_SIZE: usize;
+
+    /// Ensures that the params line up with `function`, which may have changed since the dump was made.
+    fn check(function: &UFunction) -> Result<(), SdkError> {
+        let actual = function.parms_size as usize;
+        if actual != Self::PARMS_SIZE {
+            return Err(SdkError::ParamsMismatch {
+                class: Self::CLASS,
+                function: Self::FUNCTION,
+                expected: Self::PARMS_SIZE,
+                actual,
+            });
+        }
+
+        Ok(())
+    }
+}
+
 impl<T: AsRef<UObject>> UObjectPointer<T> {
     pub fn is_same<T2>(&self, other: &UObjectPointer<T2>) -> bool
     where
@@ -59,6 +141,14 @@ impl<T: AsRef<UObject>> UObjectPointer<T> {
         var.cast().map(|it: &T2| it.into())
     }
 
+    /// Converts the pointer into a pointer to one of its ancestors, which is a no-op.
+    pub fn upcast<T2: AsRef<UObject>>(self) -> UObjectPointer<T2>
+    where
+        T: IsA<T2>,
+    {
+        UObjectPointer(self.0 as *mut T2)
+    }
+
     pub fn name(&self) -> String {
         if let Some(obj) = self.as_ref() {
             obj.as_ref().name()
@@ -68,6 +158,99 @@ impl<T: AsRef<UObject>> UObjectPointer<T> {
     }
 }
 
+impl FWeakObjectPtr {
+    /// Weakly references `object`, if the engine has assigned it a serial number.
+    ///
+    /// The engine assigns serial numbers on demand when it creates the first weak pointer to an object.
+    pub fn new(object: &UObject) -> Option<Self> {
+        UObject::all().weak_ptr(object)
+    }
+
+    /// The referenced object, unless it has been destroyed since.
+    pub fn get(&self) -> Option<&'static UObject> {
+        UObject::all().get_weak(self)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.get().is_some()
+    }
+}
+
+impl<T: AsRef<UObject>> TWeakObjectPtr<UObjectPointer<T>> {
+    /// Weakly references `object`, see [FWeakObjectPtr::new].
+    pub fn new(object: &T) -> Option<Self> {
+        Some(Self {
+            _phantom: PhantomData,
+            f_weak_object_ptr: FWeakObjectPtr::new(object.as_ref())?,
+        })
+    }
+
+    /// The referenced object, unless it has been destroyed since.
+    pub fn get(&self) -> Option<&'static T> {
+        self.f_weak_object_ptr
+            .get()
+            .map(|it| unsafe { &*(it as *const UObject as *const T) })
+    }
+
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.f_weak_object_ptr
+            .get()
+            .map(|it| unsafe { &mut *(it as *const UObject as *mut T) })
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.f_weak_object_ptr.is_valid()
+    }
+}
+
+impl<T> TLazyObjectPtr<T> {
+    /// The object the engine last resolved this pointer to, unless it has been destroyed since.
+    pub fn get_cached(&self) -> Option<&'static UObject> {
+        self.weak_ptr.get()
+    }
+}
+
+impl Display for FSoftObjectPath {
+    /// The path in the engine's notation, e.g. `/Game/Maps/Town.Town:PersistentLevel.Gate`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let asset_path = self.asset_path_name.to_raw_string().unwrap_or_default();
+        if asset_path != "None" {
+            write!(f, "{}", asset_path)?;
+        }
+
+        let sub_path = self.sub_path_string.to_string();
+        if !sub_path.is_empty() {
+            write!(f, ":{}", sub_path)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> TSoftObjectPtr<T> {
+    /// The path of the referenced asset, or an empty string for null pointers.
+    pub fn asset_path(&self) -> String {
+        self.pointer.object_id.to_string()
+    }
+
+    /// The referenced object, if it is loaded and the engine has resolved this pointer before.
+    pub fn get_cached(&self) -> Option<&'static UObject> {
+        self.pointer.get_cached()
+    }
+}
+
+impl<T> TSoftClassPtr<T> {
+    /// The path of the referenced class, or an empty string for null pointers.
+    pub fn asset_path(&self) -> String {
+        self.pointer.object_id.to_string()
+    }
+
+    /// The referenced class, if it is loaded and the engine has resolved this pointer before.
+    pub fn get_cached(&self) -> Option<&'static UClass> {
+        self.pointer.get_cached().and_then(|it| it.cast())
+    }
+}
+
 static UOBJECT: LazyLock<&'static TUObjectArray> = LazyLock::new(|| unsafe {
     ((*BASE_ADDRESS + offsets::OFFSET_GOBJECTS) as *const TUObjectArray)
         .as_ref()
@@ -218,6 +401,80 @@ impl UObject {
         fn_ptr(this, func, parms as *mut T as *mut c_void);
     }
 
+    /// Calls the exec thunk of a native function directly, skipping the ProcessEvent dispatch.
+    ///
+    /// The frame is set up like ProcessEvent does for native functions: without bytecode,
+    /// so the thunk reads its parameters from `parms` through the property chain and the out param list.
+    /// `offset` is the thunk's offset from the dump, which guards against calling a stale address.
+    ///
+    /// # Safety
+    /// The `FOutputDevice` part of the frame is zeroed, so thunks that report script errors through the frame crash.
+    pub unsafe fn call_native<T: FunctionParams>(
+        &self,
+        func: &UFunction,
+        offset: usize,
+        parms: &mut T,
+    ) -> Result<(), SdkError> {
+        if func.exec_function as usize != *BASE_ADDRESS + offset {
+            return Err(SdkError::NativeMismatch {
+                class: T::CLASS,
+                function: T::FUNCTION,
+            });
+        }
+
+        let locals = parms as *mut T as *mut u8;
+
+        // Includes the return value, just like ProcessEvent
+        let mut out_parms = func
+            .child_properties()
+            .filter(|it| it.property_flags.contains(EPropertyFlags::Parm | EPropertyFlags::OutParm))
+            .map(|it| FOutParmRec {
+                property: it as *const FProperty as *mut FProperty,
+                prop_addr: locals.add(it.offset as usize),
+                next_out_parm: null_mut(),
+            })
+            .collect::<Vec<_>>();
+
+        // The vector is not touched anymore, so the pointers into it stay valid
+        for index in 1..out_parms.len() {
+            out_parms[index - 1].next_out_parm = out_parms.as_mut_ptr().add(index);
+        }
+
+        let frame = FFrame {
+            pad_0x0000: [0; 0x10],
+            node: func,
+            object: self,
+            code: null_mut(),
+            locals: &*(locals as *const c_void),
+            most_recent_property: null_mut(),
+            most_recent_property_address: null_mut(),
+            primary_data: [0; 8],
+            secondary_data: null_mut(),
+            array_num: 0,
+            // Capacity of the inline allocator of the flow stack
+            array_max: 8,
+            previous_frame: null_mut(),
+            out_parms: if out_parms.is_empty() {
+                null_mut()
+            } else {
+                out_parms.as_mut_ptr() as *mut c_void
+            },
+            property_chain_for_compiled_in: func.child_properties as *mut UField,
+            current_native_function: null_mut(),
+            b_array_context_failed: false,
+        };
+
+        let result = if func.return_value_offset == u16::MAX {
+            null_mut()
+        } else {
+            locals.add(func.return_value_offset as usize) as *mut c_void
+        };
+
+        (func.exec_function)(self.into(), &frame, result);
+
+        Ok(())
+    }
+
     pub fn is_default_obj(&self) -> bool {
         !self.flags.contains(EObjectFlags::ClassDefaultObject)
     }
@@ -245,6 +502,14 @@ impl UStruct {
             .chain(self.iter_parents())
             .any(|it| std::ptr::eq(it, base))
     }
+
+    /// Iterates the properties declared by this struct, without those of its parents.
+    pub fn child_properties(&self) -> impl Iterator<Item = &FProperty> {
+        StructTraverser {
+            current: unsafe { (self.child_properties as *const FProperty).as_ref() },
+            get_next: &|prop| unsafe { (prop.next as *const FProperty).as_ref() },
+        }
+    }
 }
 
 impl UClass {
@@ -260,28 +525,26 @@ impl UClass {
             .map(|child| unsafe { std::mem::transmute(child) })
     }
 
+    /// Compares names by index, so `func_name` is resolved to an [FName] first.
     pub fn find_function(&self, func_name: &str) -> Option<&UFunction> {
-        once::<&UStruct>(self)
-            .chain(self.iter_parents())
-            .flat_map(|parent| parent.iter_children())
-            .filter(|child| child.has_type_flag(EClassCastFlags::Function))
-            .find(|child| child.name() == func_name)
-            .map(|child| unsafe { std::mem::transmute(child) })
+        self.find_function_by_name(&FName::find(func_name)?)
     }
 
     pub fn find_function_mut(&self, func_name: &str) -> Option<&mut UFunction> {
+        let func_name = FName::find(func_name)?;
         once::<&UStruct>(self)
             .chain(self.iter_parents())
             .flat_map(|parent| parent.iter_children())
             .filter(|child| child.has_type_flag(EClassCastFlags::Function))
-            .find(|child| child.name() == func_name)
+            .find(|child| child.name == func_name)
             .map(|child| unsafe { std::mem::transmute(child as *const UField) })
     }
 
     pub fn find(name: &str) -> Option<&'static UClass> {
         CLASS_CACHE.with(|map| {
             let class = map.entry(name.to_string()).or_insert_with(|| unsafe {
-                UObject::find_object_of_type(EClassCastFlags::Class, |obj| obj.name() == name)
+                let name = FName::find(name)?;
+                UObject::find_object_of_type(EClassCastFlags::Class, |obj| obj.name == name)
             });
 
             *class.value()
@@ -289,12 +552,44 @@ impl UClass {
     }
 }
 
-impl UFunction {
-    pub fn child_properties(&self) -> impl Iterator<Item = &FProperty> {
-        StructTraverser {
-            current: unsafe { (self.child_properties as *const FProperty).as_ref() },
-            get_next: &|prop| unsafe { (prop.next as *const FProperty).as_ref() },
+/// Caches the [UFunction] a generated wrapper resolved, per class it was looked up on.
+///
+/// Non-static wrappers look functions up on the runtime class of the object, which may override them,
+/// so a single pointer per wrapper would not suffice.
+pub struct FunctionCache {
+    functions: LazyLock<DashMap<usize, usize>>,
+}
+
+impl FunctionCache {
+    pub const fn new() -> Self {
+        Self {
+            functions: LazyLock::new(DashMap::new),
+        }
+    }
+
+    /// Like [UClass::find_function_mut], but only walks the class hierarchy on the first call per class.
+    pub fn find<'a>(&self, class: &'a UClass, func_name: &str) -> Option<&'a mut UFunction> {
+        let key = class as *const UClass as usize;
+
+        // Copy the value out so the shard lock is released before we insert below
+        let cached = self.functions.get(&key).map(|it| *it);
+        if let Some(func) = cached.and_then(|it| unsafe { (it as *mut UFunction).as_mut() }) {
+            // Classes can be unloaded and their memory reused, so make sure the function is still alive
+            if func.is_valid() {
+                return Some(func);
+            }
         }
+
+        let func = class.find_function_mut(func_name)?;
+        self.functions.insert(key, func as *mut UFunction as usize);
+
+        Some(func)
+    }
+}
+
+impl Default for FunctionCache {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -10,12 +10,14 @@ use std::sync::LazyLock;
 use bitfield::bitfield;
 use flagset::FlagSet;
 use tracing::info;
-use widestring::{decode_utf16_lossy, WideChar};
 
 pub use collections::*;
+pub use delegates::*;
 pub use enums::*;
 pub use fields::*;
 pub use functions::*;
+pub use memory::*;
+pub use properties::*;
 use crate::core_u_object::{FSoftObjectPath, UField, UFunction, UProperty};
 use crate::engine::{UWorld, UEngine};
 use crate::offsets::OFFSET_GWORLD;
@@ -23,9 +25,12 @@ use crate::offsets::OFFSET_GWORLD;
 pub use crate::core_u_object::{UClass, UObject};
 
 mod collections;
+mod delegates;
 mod enums;
 mod fields;
 mod functions;
+mod memory;
+mod properties;
 mod strings;
 mod overrides;
 
@@ -95,6 +100,14 @@ impl<T: AsRef<UObject>> Default for UObjectPointer<T> {
     }
 }
 
+/// Marks `T` as an ancestor of the implementing class (or the class itself), as reflected by Unreal.
+///
+/// Generated for every class of the SDK, so helpers can be bounded on class ancestry, e.g. `T: IsA<AActor>`.
+///
+/// # Safety
+/// The implementing type must start with `T`, which `#[extend]` guarantees for its ancestors.
+pub unsafe trait IsA<T> {}
+
 
 #[repr(C)]
 #[derive(Debug, Clone)]
@@ -111,7 +124,7 @@ bitfield! {
 #[repr(C)]
 pub union FStringData {
     pub ansi_name: [u8; 0x400],
-    pub wide_name: [WideChar; 0x400],
+    pub wide_name: [u16; 0x400],
 }
 
 #[repr(C)]
@@ -121,7 +134,7 @@ pub struct FNameEntry {
 }
 
 #[repr(C)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FName {
     pub comparison_index: i32,
     pub number: i32,
@@ -151,9 +164,9 @@ impl Display for FName {
 }
 
 #[repr(C)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct FString {
-    pub data: TArray<WideChar>,
+    pub data: TArray<u16>,
 }
 
 #[repr(C)]
@@ -183,7 +196,7 @@ pub struct FText {
 
 
 #[repr(C)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FWeakObjectPtr {
     pub object_index: i32,
     pub object_serial_number: i32,
@@ -211,31 +224,6 @@ pub struct TScriptInterface<T> {
     pub interface_pointer: *const c_void,
 }
 
-#[repr(C)]
-#[derive(Debug, Clone)]
-pub struct FMulticastSparseDelegateProperty_ {
-    _padding: [u8; 1],
-}
-
-#[repr(C)]
-#[derive(Debug, Clone)]
-pub struct FMulticastInlineDelegateProperty_ {
-    _padding: [u8; 16],
-}
-
-#[repr(C)]
-#[derive(Debug, Clone)]
-pub struct FMulticastInlineDelegate {
-    _padding: [u8; 16],
-}
-
-#[repr(C)]
-#[derive(Debug, Clone)]
-pub struct FDelegateProperty_ {
-    pub object: FWeakObjectPtr,
-    pub function_name: FName,
-}
-
 #[repr(C)]
 #[derive(Debug, Clone)]
 pub struct TSoftObjectPtr<T> {
@@ -295,6 +283,15 @@ pub struct FFrame<'a> {
     pub b_array_context_failed: bool,    // Indicates if array context failed
 }
 
+/// Entry of [FFrame::out_parms], through which thunks write their out params.
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct FOutParmRec {
+    pub property: *mut FProperty,
+    pub prop_addr: *mut u8,
+    pub next_out_parm: *mut FOutParmRec,
+}
+
 impl<'a> FFrame<'a> {
     pub unsafe fn get_params<T>(&self) -> Option<&mut T> {
         unsafe {
@@ -302,6 +299,17 @@ impl<'a> FFrame<'a> {
             t_p.as_mut()
         }
     }
+
+    /// Typed access to the parameters of the executing function, e.g. `x21::params::APyCharBase_GetHeroID`.
+    ///
+    /// Returns `None` if the frame belongs to another function or the params don't match its layout.
+    pub fn params<T: FunctionParams>(&self) -> Option<&mut T> {
+        if self.node.name() != T::FUNCTION || T::check(self.node).is_err() {
+            return None;
+        }
+
+        unsafe { self.get_params() }
+    }
 }
 
 
@@ -368,13 +376,57 @@ pub fn set_bit(value: u8, bit_offset: u8, is_set: bool) {
     }
 }
 
+#[cfg(test)]
+mod frame_tests {
+    use std::mem::{offset_of, size_of};
+    use super::*;
+
+    /// [UObject::call_native] hands this layout to the exec thunks, which read it as UE's `FFrame`.
+    #[test]
+    fn test_frame() {
+        assert_eq!(offset_of!(FFrame, node), 0x10);
+        assert_eq!(offset_of!(FFrame, object), 0x18);
+        assert_eq!(offset_of!(FFrame, code), 0x20);
+        assert_eq!(offset_of!(FFrame, locals), 0x28);
+        assert_eq!(offset_of!(FFrame, most_recent_property), 0x30);
+        assert_eq!(offset_of!(FFrame, most_recent_property_address), 0x38);
+        assert_eq!(offset_of!(FFrame, primary_data), 0x40);
+        assert_eq!(offset_of!(FFrame, array_num), 0x68);
+        assert_eq!(offset_of!(FFrame, array_max), 0x6C);
+        assert_eq!(offset_of!(FFrame, previous_frame), 0x70);
+        assert_eq!(offset_of!(FFrame, out_parms), 0x78);
+        assert_eq!(offset_of!(FFrame, property_chain_for_compiled_in), 0x80);
+        assert_eq!(offset_of!(FFrame, current_native_function), 0x88);
+        assert_eq!(offset_of!(FFrame, b_array_context_failed), 0x90);
+        assert_eq!(size_of::<FFrame>(), 0x98);
+    }
+
+    #[test]
+    fn test_out_parm_rec() {
+        assert_eq!(offset_of!(FOutParmRec, property), 0x0);
+        assert_eq!(offset_of!(FOutParmRec, prop_addr), 0x8);
+        assert_eq!(offset_of!(FOutParmRec, next_out_parm), 0x10);
+        assert_eq!(size_of::<FOutParmRec>(), 0x18);
+    }
+
+    #[test]
+    fn test_property_offset() {
+        assert_eq!(offset_of!(FProperty, property_flags), 0x40);
+        assert_eq!(offset_of!(FProperty, offset), 0x4C);
+    }
+}
+
 #[cfg(test)]
 mod collection_tests {
     use std::mem::size_of;
     use super::*;
 
     #[test]
-    fn test_f_string() {}
+    fn test_f_string() {
+        let string = FString::from("Visions of Mana");
+        assert_eq!(string.data.len(), 16, "FString is missing its terminator!");
+        assert_eq!(string.to_string(), "Visions of Mana");
+    }
 
     #[test]
     fn test_collections() {
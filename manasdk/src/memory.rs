@@ -0,0 +1,200 @@
+use std::alloc::Layout;
+use std::ffi::c_void;
+use std::sync::OnceLock;
+use tracing::warn;
+
+/// The allocator behind the buffers of [TArray](crate::TArray) and [FString](crate::FString).
+///
+/// The engine reallocates and frees these buffers through `FMemory`, so anything that is handed to the game
+/// has to come from [GMalloc].
+pub trait UnrealAllocator: Send + Sync {
+    /// Like `FMemory::Malloc`, returns null if the allocation failed.
+    fn malloc(&self, size: usize, alignment: usize) -> *mut c_void;
+
+    /// Like `FMemory::Realloc`, which moves the contents if needed and frees `original`.
+    ///
+    /// # Safety
+    /// `original` must be null or have been allocated by this allocator.
+    unsafe fn realloc(&self, original: *mut c_void, size: usize, alignment: usize) -> *mut c_void;
+
+    /// # Safety
+    /// `original` must be null or have been allocated by this allocator.
+    unsafe fn free(&self, original: *mut c_void);
+}
+
+static ALLOCATOR: OnceLock<Box<dyn UnrealAllocator>> = OnceLock::new();
+
+/// Sets the allocator of all [TArray](crate::TArray) and [FString](crate::FString) buffers, which can only be done once.
+pub fn set_allocator(allocator: impl UnrealAllocator + 'static) {
+    if ALLOCATOR.set(Box::new(allocator)).is_err() {
+        warn!("An allocator has already been set up");
+    }
+}
+
+/// The allocator passed to [set_allocator], or the [SystemAllocator] when running tests.
+pub fn allocator() -> Option<&'static dyn UnrealAllocator> {
+    #[cfg(test)]
+    let allocator = Some(ALLOCATOR.get_or_init(|| Box::new(SystemAllocator)));
+    #[cfg(not(test))]
+    let allocator = ALLOCATOR.get();
+
+    allocator.map(|it| it.as_ref())
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct FMalloc {
+    vtable: *const FMallocVTable,
+}
+
+/// The virtual functions of UE4's `FMalloc`, which come after the destructor and `FExec::Exec`.
+#[repr(C)]
+#[derive(Debug)]
+struct FMallocVTable {
+    _destructor: usize,
+    _exec: usize,
+    malloc: extern "C" fn(this: *mut FMalloc, count: usize, alignment: u32) -> *mut c_void,
+    _try_malloc: usize,
+    realloc: extern "C" fn(this: *mut FMalloc, original: *mut c_void, count: usize, alignment: u32) -> *mut c_void,
+    _try_realloc: usize,
+    free: extern "C" fn(this: *mut FMalloc, original: *mut c_void),
+}
+
+/// The engine's global allocator, which `FMemory` forwards to.
+#[derive(Debug)]
+pub struct GMalloc {
+    global: *const *mut FMalloc,
+}
+
+unsafe impl Send for GMalloc {}
+unsafe impl Sync for GMalloc {}
+
+impl GMalloc {
+    /// # Safety
+    /// `global` must be the address of the game's `GMalloc` variable.
+    pub unsafe fn new(global: *const *mut FMalloc) -> Self {
+        Self { global }
+    }
+
+    fn instance(&self) -> Option<(*mut FMalloc, &FMallocVTable)> {
+        unsafe {
+            let instance = *self.global;
+            let vtable = instance.as_ref()?.vtable.as_ref()?;
+            Some((instance, vtable))
+        }
+    }
+}
+
+impl UnrealAllocator for GMalloc {
+    fn malloc(&self, size: usize, alignment: usize) -> *mut c_void {
+        match self.instance() {
+            Some((instance, vtable)) => (vtable.malloc)(instance, size, alignment as u32),
+            None => std::ptr::null_mut(),
+        }
+    }
+
+    unsafe fn realloc(&self, original: *mut c_void, size: usize, alignment: usize) -> *mut c_void {
+        match self.instance() {
+            Some((instance, vtable)) => (vtable.realloc)(instance, original, size, alignment as u32),
+            None => std::ptr::null_mut(),
+        }
+    }
+
+    unsafe fn free(&self, original: *mut c_void) {
+        match self.instance() {
+            Some((instance, vtable)) => (vtable.free)(instance, original),
+            None => warn!("GMalloc is not initialized, leaking {:?}", original),
+        }
+    }
+}
+
+/// Rust's global allocator, for buffers that never reach the engine.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemAllocator;
+
+impl SystemAllocator {
+    /// `free` gets no size, so the size and alignment are stored in front of every block.
+    const HEADER_SIZE: usize = 2 * size_of::<usize>();
+
+    fn layout(size: usize, alignment: usize) -> Option<Layout> {
+        let alignment = alignment.max(Self::HEADER_SIZE);
+        Layout::from_size_align(size.checked_add(alignment)?, alignment).ok()
+    }
+
+    /// # Safety
+    /// `block` must have been allocated by this allocator.
+    unsafe fn header(block: *mut c_void) -> *mut [usize; 2] {
+        (block as *mut u8).sub(Self::HEADER_SIZE) as *mut [usize; 2]
+    }
+}
+
+impl UnrealAllocator for SystemAllocator {
+    fn malloc(&self, size: usize, alignment: usize) -> *mut c_void {
+        let Some(layout) = Self::layout(size, alignment) else {
+            return std::ptr::null_mut();
+        };
+
+        unsafe {
+            let allocation = std::alloc::alloc(layout);
+            if allocation.is_null() {
+                return std::ptr::null_mut();
+            }
+
+            let block = allocation.add(layout.align()) as *mut c_void;
+            Self::header(block).write([size, layout.align()]);
+            block
+        }
+    }
+
+    unsafe fn realloc(&self, original: *mut c_void, size: usize, alignment: usize) -> *mut c_void {
+        if original.is_null() {
+            return self.malloc(size, alignment);
+        }
+
+        if size == 0 {
+            self.free(original);
+            return std::ptr::null_mut();
+        }
+
+        let block = self.malloc(size, alignment);
+        if !block.is_null() {
+            let [original_size, _] = Self::header(original).read();
+            std::ptr::copy_nonoverlapping(original as *const u8, block as *mut u8, original_size.min(size));
+            self.free(original);
+        }
+
+        block
+    }
+
+    unsafe fn free(&self, original: *mut c_void) {
+        if original.is_null() {
+            return;
+        }
+
+        let [size, alignment] = Self::header(original).read();
+        let layout = Layout::from_size_align_unchecked(size + alignment, alignment);
+        std::alloc::dealloc((original as *mut u8).sub(alignment), layout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_allocator() {
+        let allocator = SystemAllocator;
+        unsafe {
+            let block = allocator.malloc(4, 64) as *mut u32;
+            assert_eq!(block as usize % 64, 0);
+            block.write(0xDEADBEEF);
+
+            let block = allocator.realloc(block as *mut c_void, 1024, 64) as *mut u32;
+            assert_eq!(block as usize % 64, 0);
+            assert_eq!(block.read(), 0xDEADBEEF);
+
+            assert!(allocator.realloc(block as *mut c_void, 0, 64).is_null());
+            allocator.free(std::ptr::null_mut());
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use flagset::FlagSet;
 use manasdk_macros::{extend, HasClassObject};
-use crate::{EClassCastFlags, EFunctionFlags, EObjectFlags, EPropertyFlags, FField, FName, FNativeFuncPtr, UObjectPointer};
+use crate::{EClassCastFlags, EFunctionFlags, EObjectFlags, EPropertyFlags, FField, FName, FNativeFuncPtr, IsA, UObjectPointer};
 use crate::core_u_object::{FVector, FVector2D};
 
 #[repr(C)]
@@ -20,6 +20,8 @@ impl AsRef<UObject> for UObject {
     }
 }
 
+unsafe impl IsA<UObject> for UObject {}
+
 #[repr(C)]
 #[extend(UObject)]
 #[derive(Debug, Clone, HasClassObject)]
@@ -27,6 +29,9 @@ pub struct UField {
     pub next: UObjectPointer<UField>,
 }
 
+unsafe impl IsA<UField> for UField {}
+unsafe impl IsA<UObject> for UField {}
+
 #[repr(C)]
 #[extend(UField)]
 #[derive(Debug, Clone, HasClassObject)]
@@ -40,6 +45,10 @@ pub struct UStruct {
     pub _padding_201: [u8; 0x50],
 }
 
+unsafe impl IsA<UStruct> for UStruct {}
+unsafe impl IsA<UField> for UStruct {}
+unsafe impl IsA<UObject> for UStruct {}
+
 #[repr(C)]
 #[extend(UStruct)]
 #[derive(Debug, Clone, HasClassObject)]
@@ -51,6 +60,11 @@ pub struct UClass {
     pub _pad_3: [u8; 0x110],
 }
 
+unsafe impl IsA<UClass> for UClass {}
+unsafe impl IsA<UStruct> for UClass {}
+unsafe impl IsA<UField> for UClass {}
+unsafe impl IsA<UObject> for UClass {}
+
 
 #[repr(C)]
 #[extend(UStruct)]
@@ -65,6 +79,11 @@ pub struct UFunction {
     pub exec_function: FNativeFuncPtr,
 }
 
+unsafe impl IsA<UFunction> for UFunction {}
+unsafe impl IsA<UStruct> for UFunction {}
+unsafe impl IsA<UField> for UFunction {}
+unsafe impl IsA<UObject> for UFunction {}
+
 #[repr(C)]
 #[extend(UField)]
 #[derive(Debug, Clone, HasClassObject)]
@@ -79,6 +98,10 @@ pub struct UProperty {
     pub _padding_200: [u8; 33usize],
 }
 
+unsafe impl IsA<UProperty> for UProperty {}
+unsafe impl IsA<UField> for UProperty {}
+unsafe impl IsA<UObject> for UProperty {}
+
 impl FVector {
     pub fn new(x: f32, y: f32, z: f32) -> Self {
         Self {
@@ -0,0 +1,521 @@
+use std::ffi::c_void;
+use std::fmt::{Display, Formatter};
+use std::iter::once;
+use flagset::FlagSet;
+use crate::core_u_object::UStruct;
+use crate::{
+    EClassCastFlags, FArrayProperty, FBoolProperty, FEnumProperty, FField, FFieldClass, FMapProperty,
+    FName, FObjectPropertyBase, FProperty, FSetProperty, FString, FText, TArray, TMap, TSet, TWeakObjectPtr, UObject,
+    UObjectPointer,
+};
+
+/// A Rust type that the value of an [FProperty] can be read as.
+///
+/// # Safety
+/// [PropertyType::matches] must only accept properties whose values are laid out like `Self`.
+pub unsafe trait PropertyType: Sized {
+    /// The flag that the [FFieldClass] of a matching property has.
+    const CAST_FLAG: EClassCastFlags;
+
+    fn matches(property: &FProperty) -> bool {
+        property.has_type_flag(Self::CAST_FLAG) && property.element_size as usize == size_of::<Self>()
+    }
+}
+
+macro_rules! impl_property_type {
+    ($($ty:ty => $flag:ident),*) => {
+        $(unsafe impl PropertyType for $ty {
+            const CAST_FLAG: EClassCastFlags = EClassCastFlags::$flag;
+        })*
+    };
+}
+
+impl_property_type!(
+    i8 => Int8Property,
+    u8 => ByteProperty,
+    i16 => Int16Property,
+    u16 => UInt16Property,
+    i32 => IntProperty,
+    u32 => UInt32Property,
+    i64 => Int64Property,
+    u64 => UInt64Property,
+    f32 => FloatProperty,
+    f64 => DoubleProperty,
+    FName => NameProperty,
+    FString => StrProperty,
+    FText => TextProperty
+);
+
+/// Only native bools, bitfields have to be accessed through [PropertyValue::Bool].
+unsafe impl PropertyType for bool {
+    const CAST_FLAG: EClassCastFlags = EClassCastFlags::BoolProperty;
+
+    fn matches(property: &FProperty) -> bool {
+        property.has_type_flag(Self::CAST_FLAG)
+            && property.element_size == 1
+            && unsafe { property.cast_unchecked::<FBoolProperty>() }.field_mask == 0xFF
+    }
+}
+
+/// The class of the object is not checked, use [UObjectPointer::cast] for that.
+unsafe impl<T: AsRef<UObject>> PropertyType for UObjectPointer<T> {
+    const CAST_FLAG: EClassCastFlags = EClassCastFlags::ObjectProperty;
+}
+
+unsafe impl<T: AsRef<UObject>> PropertyType for TWeakObjectPtr<UObjectPointer<T>> {
+    const CAST_FLAG: EClassCastFlags = EClassCastFlags::WeakObjectProperty;
+}
+
+unsafe impl<T: PropertyType> PropertyType for TArray<T> {
+    const CAST_FLAG: EClassCastFlags = EClassCastFlags::ArrayProperty;
+
+    fn matches(property: &FProperty) -> bool {
+        property.has_type_flag(Self::CAST_FLAG)
+            && unsafe { property.cast_unchecked::<FArrayProperty>().inner_property.as_ref() }
+                .is_some_and(|inner| T::matches(inner))
+    }
+}
+
+unsafe impl<T: PropertyType> PropertyType for TSet<T> {
+    const CAST_FLAG: EClassCastFlags = EClassCastFlags::SetProperty;
+
+    fn matches(property: &FProperty) -> bool {
+        property.has_type_flag(Self::CAST_FLAG)
+            && unsafe { property.cast_unchecked::<FSetProperty>().element_property.as_ref() }
+                .is_some_and(|element| T::matches(element))
+    }
+}
+
+unsafe impl<K: PropertyType, V: PropertyType> PropertyType for TMap<K, V> {
+    const CAST_FLAG: EClassCastFlags = EClassCastFlags::MapProperty;
+
+    fn matches(property: &FProperty) -> bool {
+        if !property.has_type_flag(Self::CAST_FLAG) {
+            return false;
+        }
+
+        let property = unsafe { property.cast_unchecked::<FMapProperty>() };
+        unsafe { property.key_property.as_ref() }.is_some_and(|key| K::matches(key))
+            && unsafe { property.value_property.as_ref() }.is_some_and(|value| V::matches(value))
+    }
+}
+
+/// The value of a property whose type is only known at runtime.
+///
+/// Structs, containers and delegates have no untyped representation, use [UObject::get_property] for those.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    Bool(bool),
+    Int8(i8),
+    /// Also used for enums, whose underlying property is usually a byte.
+    Byte(u8),
+    Int16(i16),
+    UInt16(u16),
+    Int(i32),
+    UInt32(u32),
+    Int64(i64),
+    UInt64(u64),
+    Float(f32),
+    Double(f64),
+    Name(FName),
+    Str(String),
+    /// Read-only, the text is not written back.
+    Text(String),
+    Object(UObjectPointer<UObject>),
+}
+
+impl Display for PropertyValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertyValue::Bool(value) => write!(f, "{}", value),
+            PropertyValue::Int8(value) => write!(f, "{}", value),
+            PropertyValue::Byte(value) => write!(f, "{}", value),
+            PropertyValue::Int16(value) => write!(f, "{}", value),
+            PropertyValue::UInt16(value) => write!(f, "{}", value),
+            PropertyValue::Int(value) => write!(f, "{}", value),
+            PropertyValue::UInt32(value) => write!(f, "{}", value),
+            PropertyValue::Int64(value) => write!(f, "{}", value),
+            PropertyValue::UInt64(value) => write!(f, "{}", value),
+            PropertyValue::Float(value) => write!(f, "{}", value),
+            PropertyValue::Double(value) => write!(f, "{}", value),
+            PropertyValue::Name(value) => write!(f, "{}", value),
+            PropertyValue::Str(value) | PropertyValue::Text(value) => write!(f, "{:?}", value),
+            PropertyValue::Object(value) => write!(f, "{}", value.name()),
+        }
+    }
+}
+
+impl FField {
+    pub fn class(&self) -> Option<&FFieldClass> {
+        unsafe { self.class_private.as_ref() }
+    }
+
+    pub fn has_type_flag(&self, flags: impl Into<FlagSet<EClassCastFlags>>) -> bool {
+        self.class()
+            .map(|it| it.cast_flags.contains(flags))
+            .unwrap_or_default()
+    }
+}
+
+impl FProperty {
+    /// # Safety
+    /// The class of the property must be `T` or one of its descendants.
+    unsafe fn cast_unchecked<T>(&self) -> &T {
+        &*(self as *const Self as *const T)
+    }
+
+    /// Typed access to the value of this property in `container`, or `None` if it isn't a `T`.
+    ///
+    /// # Safety
+    /// `container` must point to an instance of the struct that owns this property.
+    pub unsafe fn value<'a, T: PropertyType>(&self, container: *const c_void) -> Option<&'a T> {
+        if T::matches(self) {
+            (container.byte_add(self.offset as usize) as *const T).as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// # Safety
+    /// `container` must point to an instance of the struct that owns this property.
+    pub unsafe fn value_mut<'a, T: PropertyType>(&self, container: *mut c_void) -> Option<&'a mut T> {
+        if T::matches(self) {
+            (container.byte_add(self.offset as usize) as *mut T).as_mut()
+        } else {
+            None
+        }
+    }
+
+    /// Reads the value of this property in `container`, or `None` if its type has no [PropertyValue].
+    ///
+    /// # Safety
+    /// `container` must point to an instance of the struct that owns this property.
+    pub unsafe fn get_value(&self, container: *const c_void) -> Option<PropertyValue> {
+        self.read(container.byte_add(self.offset as usize) as *const u8)
+    }
+
+    /// Writes `value` into `container`, which fails if the value doesn't have the type of this property.
+    ///
+    /// # Safety
+    /// `container` must point to an instance of the struct that owns this property.
+    pub unsafe fn set_value(&self, container: *mut c_void, value: PropertyValue) -> bool {
+        self.write(container.byte_add(self.offset as usize) as *mut u8, value)
+    }
+
+    unsafe fn read(&self, value: *const u8) -> Option<PropertyValue> {
+        let flags = self.class()?.cast_flags;
+
+        let result = if flags.contains(EClassCastFlags::BoolProperty) {
+            let property = self.cast_unchecked::<FBoolProperty>();
+            PropertyValue::Bool(*value.add(property.byte_offset as usize) & property.field_mask != 0)
+        } else if flags.contains(EClassCastFlags::EnumProperty) {
+            return self.cast_unchecked::<FEnumProperty>().underlying_property.as_ref()?.read(value);
+        } else if flags.contains(EClassCastFlags::Int8Property) {
+            PropertyValue::Int8(*(value as *const i8))
+        } else if flags.contains(EClassCastFlags::ByteProperty) {
+            PropertyValue::Byte(*value)
+        } else if flags.contains(EClassCastFlags::Int16Property) {
+            PropertyValue::Int16(*(value as *const i16))
+        } else if flags.contains(EClassCastFlags::UInt16Property) {
+            PropertyValue::UInt16(*(value as *const u16))
+        } else if flags.contains(EClassCastFlags::IntProperty) {
+            PropertyValue::Int(*(value as *const i32))
+        } else if flags.contains(EClassCastFlags::UInt32Property) {
+            PropertyValue::UInt32(*(value as *const u32))
+        } else if flags.contains(EClassCastFlags::Int64Property) {
+            PropertyValue::Int64(*(value as *const i64))
+        } else if flags.contains(EClassCastFlags::UInt64Property) {
+            PropertyValue::UInt64(*(value as *const u64))
+        } else if flags.contains(EClassCastFlags::FloatProperty) {
+            PropertyValue::Float(*(value as *const f32))
+        } else if flags.contains(EClassCastFlags::DoubleProperty) {
+            PropertyValue::Double(*(value as *const f64))
+        } else if flags.contains(EClassCastFlags::NameProperty) {
+            PropertyValue::Name((*(value as *const FName)).clone())
+        } else if flags.contains(EClassCastFlags::StrProperty) {
+            PropertyValue::Str((*(value as *const FString)).to_string())
+        } else if flags.contains(EClassCastFlags::TextProperty) {
+            PropertyValue::Text((*(value as *const FText)).to_string())
+        } else if flags.contains(EClassCastFlags::ObjectProperty) {
+            PropertyValue::Object((*(value as *const UObjectPointer<UObject>)).clone())
+        } else {
+            return None;
+        };
+
+        Some(result)
+    }
+
+    unsafe fn write(&self, value: *mut u8, new_value: PropertyValue) -> bool {
+        let Some(class) = self.class() else {
+            return false;
+        };
+
+        let flags = class.cast_flags;
+        if flags.contains(EClassCastFlags::EnumProperty) {
+            return match self.cast_unchecked::<FEnumProperty>().underlying_property.as_ref() {
+                Some(underlying) => underlying.write(value, new_value),
+                None => false,
+            };
+        }
+
+        match new_value {
+            PropertyValue::Bool(new_value) if flags.contains(EClassCastFlags::BoolProperty) => {
+                // Like FBoolProperty::SetPropertyValue, which leaves the other bits of the byte alone
+                let property = self.cast_unchecked::<FBoolProperty>();
+                let byte = value.add(property.byte_offset as usize);
+                *byte = (*byte & !property.field_mask) | if new_value { property.byte_mask } else { 0 };
+            }
+            PropertyValue::Int8(new_value) if flags.contains(EClassCastFlags::Int8Property) => {
+                *(value as *mut i8) = new_value;
+            }
+            PropertyValue::Byte(new_value) if flags.contains(EClassCastFlags::ByteProperty) => {
+                *value = new_value;
+            }
+            PropertyValue::Int16(new_value) if flags.contains(EClassCastFlags::Int16Property) => {
+                *(value as *mut i16) = new_value;
+            }
+            PropertyValue::UInt16(new_value) if flags.contains(EClassCastFlags::UInt16Property) => {
+                *(value as *mut u16) = new_value;
+            }
+            PropertyValue::Int(new_value) if flags.contains(EClassCastFlags::IntProperty) => {
+                *(value as *mut i32) = new_value;
+            }
+            PropertyValue::UInt32(new_value) if flags.contains(EClassCastFlags::UInt32Property) => {
+                *(value as *mut u32) = new_value;
+            }
+            PropertyValue::Int64(new_value) if flags.contains(EClassCastFlags::Int64Property) => {
+                *(value as *mut i64) = new_value;
+            }
+            PropertyValue::UInt64(new_value) if flags.contains(EClassCastFlags::UInt64Property) => {
+                *(value as *mut u64) = new_value;
+            }
+            PropertyValue::Float(new_value) if flags.contains(EClassCastFlags::FloatProperty) => {
+                *(value as *mut f32) = new_value;
+            }
+            PropertyValue::Double(new_value) if flags.contains(EClassCastFlags::DoubleProperty) => {
+                *(value as *mut f64) = new_value;
+            }
+            PropertyValue::Name(new_value) if flags.contains(EClassCastFlags::NameProperty) => {
+                *(value as *mut FName) = new_value;
+            }
+            PropertyValue::Str(new_value) if flags.contains(EClassCastFlags::StrProperty) => {
+                // Drops the previous string, which frees its buffer through the allocator
+                *(value as *mut FString) = FString::from(new_value.as_str());
+            }
+            PropertyValue::Object(new_value) if flags.contains(EClassCastFlags::ObjectProperty) => {
+                // The engine assumes that the object is of the property class
+                let property_class = &self.cast_unchecked::<FObjectPropertyBase>().property_class;
+                let fits = match (new_value.as_ref(), property_class.as_ref()) {
+                    (None, _) => true,
+                    (Some(object), Some(class)) => object.is_a(class),
+                    (Some(_), None) => false,
+                };
+
+                if !fits {
+                    return false;
+                }
+
+                *(value as *mut UObjectPointer<UObject>) = new_value;
+            }
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl UStruct {
+    /// Iterates the properties of this struct, followed by those of its parents.
+    pub fn iter_properties(&self) -> impl Iterator<Item = &FProperty> {
+        once(self)
+            .chain(self.iter_parents())
+            .flat_map(|it| it.child_properties())
+    }
+
+    pub fn find_property(&self, name: &str) -> Option<&FProperty> {
+        let name = FName::find(name)?;
+        self.iter_properties().find(|it| it.name == name)
+    }
+}
+
+impl UObject {
+    /// Finds a property of the class of this object, e.g. one that isn't part of the generated SDK.
+    pub fn find_property(&self, name: &str) -> Option<&FProperty> {
+        self.class.as_ref()?.find_property(name)
+    }
+
+    /// Typed access to a property by name, or `None` if there is no such property or it isn't a `T`.
+    pub fn get_property<T: PropertyType>(&self, name: &str) -> Option<&T> {
+        unsafe { self.find_property(name)?.value(self as *const Self as *const c_void) }
+    }
+
+    pub fn get_property_mut<T: PropertyType>(&mut self, name: &str) -> Option<&mut T> {
+        let property = self.find_property(name)? as *const FProperty;
+        unsafe { (*property).value_mut(self as *mut Self as *mut c_void) }
+    }
+
+    /// Reads a property by name without knowing its type.
+    pub fn get_value(&self, name: &str) -> Option<PropertyValue> {
+        unsafe { self.find_property(name)?.get_value(self as *const Self as *const c_void) }
+    }
+
+    /// Writes a property by name, which fails if there is no such property or `value` doesn't have its type.
+    pub fn set_value(&mut self, name: &str, value: PropertyValue) -> bool {
+        let Some(property) = self.find_property(name).map(|it| it as *const FProperty) else {
+            return false;
+        };
+
+        unsafe { (*property).set_value(self as *mut Self as *mut c_void, value) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::{offset_of, zeroed};
+    use super::*;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct Container {
+        health: i32,
+        speed: f32,
+        bitfield: u8,
+        is_dead: bool,
+        title: FString,
+        items: TArray<i32>,
+    }
+
+    fn field_class(flags: impl Into<FlagSet<EClassCastFlags>>) -> Box<FFieldClass> {
+        let mut class: Box<FFieldClass> = Box::new(unsafe { zeroed() });
+        class.cast_flags = flags.into() | EClassCastFlags::Field | EClassCastFlags::Property;
+        class
+    }
+
+    /// A zeroed property of type `P`, which has to start with an [FProperty].
+    fn property<P>(class: &FFieldClass, offset: usize, size: usize) -> Box<P> {
+        unsafe {
+            let mut property: Box<P> = Box::new(zeroed());
+            let base = &mut *(property.as_mut() as *mut P as *mut FProperty);
+            base.class_private = class as *const FFieldClass as *mut FFieldClass;
+            base.array_dim = 1;
+            base.element_size = size as i32;
+            base.offset = offset as i32;
+            property
+        }
+    }
+
+    fn bool_property(class: &FFieldClass, offset: usize, mask: u8) -> Box<FBoolProperty> {
+        let mut property: Box<FBoolProperty> = property(class, offset, 1);
+        property.field_size = 1;
+        property.byte_mask = mask;
+        property.field_mask = mask;
+        property
+    }
+
+    fn link(properties: &mut [&mut FProperty]) -> *const FField {
+        let fields = properties.iter_mut().map(|it| &mut ***it as *mut FField).collect::<Vec<_>>();
+        for pair in fields.windows(2) {
+            unsafe { (*pair[0]).next = pair[1] };
+        }
+
+        fields.first().map_or(std::ptr::null(), |it| *it as *const FField)
+    }
+
+    #[test]
+    fn test_iter_properties() {
+        let int_class = field_class(EClassCastFlags::IntProperty);
+        let float_class = field_class(EClassCastFlags::FloatProperty);
+        let mut health: Box<FProperty> = property(&int_class, offset_of!(Container, health), 4);
+        let mut speed: Box<FProperty> = property(&float_class, offset_of!(Container, speed), 4);
+        let mut title: Box<FProperty> = property(&int_class, offset_of!(Container, title), 4);
+
+        let mut parent: Box<UStruct> = Box::new(unsafe { zeroed() });
+        let mut child: Box<UStruct> = Box::new(unsafe { zeroed() });
+        parent.child_properties = link(&mut [&mut *health, &mut *speed]);
+        child.child_properties = link(&mut [&mut *title]);
+        child.super_ = (&*parent).into();
+
+        let offsets = child.iter_properties().map(|it| it.offset as usize).collect::<Vec<_>>();
+        assert_eq!(
+            offsets,
+            vec![offset_of!(Container, title), offset_of!(Container, health), offset_of!(Container, speed)]
+        );
+        assert_eq!(child.child_properties().count(), 1);
+    }
+
+    #[test]
+    fn test_typed_access() {
+        let int_class = field_class(EClassCastFlags::IntProperty | EClassCastFlags::NumericProperty);
+        let bool_class = field_class(EClassCastFlags::BoolProperty);
+        let array_class = field_class(EClassCastFlags::ArrayProperty);
+        let health: Box<FProperty> = property(&int_class, offset_of!(Container, health), 4);
+        let is_dead = bool_property(&bool_class, offset_of!(Container, is_dead), 0xFF);
+        let bitfield = bool_property(&bool_class, offset_of!(Container, bitfield), 0x02);
+        let inner: Box<FProperty> = property(&int_class, 0, 4);
+        let mut items: Box<FArrayProperty> = property(&array_class, offset_of!(Container, items), 0x10);
+        items.inner_property = inner.as_ref() as *const FProperty as *mut FProperty;
+
+        let mut container = Container {
+            health: 100,
+            is_dead: true,
+            items: TArray::from_iter([1, 2, 3]),
+            ..Default::default()
+        };
+        let pointer = &mut container as *mut Container as *mut c_void;
+
+        unsafe {
+            assert_eq!(health.value::<i32>(pointer), Some(&100));
+            assert_eq!(health.value::<f32>(pointer), None);
+            assert_eq!(health.value::<u32>(pointer), None);
+            assert_eq!(is_dead.value::<bool>(pointer), Some(&true));
+            assert_eq!(bitfield.value::<bool>(pointer), None, "Bitfields cannot be referenced");
+
+            assert_eq!(items.value::<TArray<i32>>(pointer).map(|it| it.as_slice()), Some(&[1, 2, 3][..]));
+            assert!(items.value::<TArray<f32>>(pointer).is_none());
+
+            *health.value_mut::<i32>(pointer).unwrap() = 50;
+        }
+
+        assert_eq!(container.health, 50);
+    }
+
+    #[test]
+    fn test_property_values() {
+        let int_class = field_class(EClassCastFlags::IntProperty | EClassCastFlags::NumericProperty);
+        let bool_class = field_class(EClassCastFlags::BoolProperty);
+        let str_class = field_class(EClassCastFlags::StrProperty);
+        let array_class = field_class(EClassCastFlags::ArrayProperty);
+        let health: Box<FProperty> = property(&int_class, offset_of!(Container, health), 4);
+        let first_bit = bool_property(&bool_class, offset_of!(Container, bitfield), 0x01);
+        let second_bit = bool_property(&bool_class, offset_of!(Container, bitfield), 0x02);
+        let title: Box<FProperty> = property(&str_class, offset_of!(Container, title), 0x10);
+        let items: Box<FProperty> = property(&array_class, offset_of!(Container, items), 0x10);
+
+        let mut container = Container {
+            health: 100,
+            bitfield: 0b1000_0010,
+            title: FString::from("Mana"),
+            ..Default::default()
+        };
+        let pointer = &mut container as *mut Container as *mut c_void;
+
+        unsafe {
+            assert_eq!(health.get_value(pointer), Some(PropertyValue::Int(100)));
+            assert_eq!(first_bit.get_value(pointer), Some(PropertyValue::Bool(false)));
+            assert_eq!(second_bit.get_value(pointer), Some(PropertyValue::Bool(true)));
+            assert_eq!(title.get_value(pointer), Some(PropertyValue::Str("Mana".to_string())));
+            assert_eq!(items.get_value(pointer), None);
+
+            assert!(health.set_value(pointer, PropertyValue::Int(25)));
+            assert!(!health.set_value(pointer, PropertyValue::Float(25.0)));
+            assert!(first_bit.set_value(pointer, PropertyValue::Bool(true)));
+            assert!(second_bit.set_value(pointer, PropertyValue::Bool(false)));
+            assert!(title.set_value(pointer, PropertyValue::Str("Visions of Mana".to_string())));
+            assert!(!items.set_value(pointer, PropertyValue::Int(0)));
+        }
+
+        assert_eq!(container.health, 25);
+        assert_eq!(container.bitfield, 0b1000_0001, "Other bits of the bitfield were touched");
+        assert_eq!(container.title.to_string(), "Visions of Mana");
+    }
+}
@@ -1,13 +1,14 @@
 use std::fmt::{Display, Formatter};
 use std::ops::AddAssign;
+use std::sync::{LazyLock, Mutex, PoisonError};
 use tracing::info;
-use widestring::{decode_utf16_lossy, WideStr};
-use crate::{FName, FNameEntry, FNamePool, FString, FText};
+use widestring::{decode_utf16_lossy, U16Str};
+use crate::{FName, FNameEntry, FNameEntryHeader, FNameIndex, FNamePool, FString, FText};
 
 #[derive(Copy, Clone, Debug)]
 pub enum UnrealString<'a> {
     Ascii(&'a str),
-    Wide(&'a WideStr),
+    Wide(&'a U16Str),
 }
 
 impl<'a> UnrealString<'a> {
@@ -33,7 +34,7 @@ impl<'a> UnrealString<'a> {
         }
     }
 
-    pub fn as_widestr(&self) -> Option<&WideStr> {
+    pub fn as_widestr(&self) -> Option<&U16Str> {
         if let Self::Wide(as_str) = self {
             Some(as_str)
         } else {
@@ -47,10 +48,17 @@ impl FNameEntry {
         self.header.b_is_wide() > 0
     }
 
+    /// Bytes the entry takes up in its pool block, which aligns entries to 2 bytes.
+    pub fn size(&self) -> usize {
+        let char_size = if self.is_wide() { 2 } else { 1 };
+        let size = size_of::<FNameEntryHeader>() + self.header.len() as usize * char_size;
+        size.next_multiple_of(2)
+    }
+
     pub fn get_string(&self) -> UnrealString {
         let len = self.header.len() as usize;
         if self.is_wide() {
-            UnrealString::Wide(unsafe { WideStr::from_slice(&self.name.wide_name[0..len]) })
+            UnrealString::Wide(U16Str::from_slice(unsafe { &self.name.wide_name[0..len] }))
         } else {
             UnrealString::Ascii(unsafe { std::mem::transmute(&self.name.ansi_name[0..len]) })
         }
@@ -62,6 +70,17 @@ impl FName {
         self.comparison_index as u32
     }
 
+    /// The name of `value`, if the engine knows the string, like `FName(..., FNAME_Find)`.
+    ///
+    /// Strings are looked up case-insensitively in an index that is built on the first call and catches up with
+    /// names added since.
+    pub fn find(value: &str) -> Option<FName> {
+        static INDEX: LazyLock<Mutex<FNameIndex>> = LazyLock::new(Default::default);
+
+        let mut index = INDEX.lock().unwrap_or_else(PoisonError::into_inner);
+        index.find_name(FNamePool::get(), value)
+    }
+
     pub fn to_raw_string(&self) -> Option<String> {
         if self.comparison_index < 0 {
             return None;
@@ -90,11 +109,21 @@ impl FName {
 
 impl Display for FString {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let str = decode_utf16_lossy(self.data.iter().copied()).collect::<String>();
+        // The length of engine strings includes the terminator
+        let str = decode_utf16_lossy(self.data.iter().copied().take_while(|&it| it != 0)).collect::<String>();
         write!(f, "{str}")
     }
 }
 
+impl From<&str> for FString {
+    /// Encodes `value` as UTF-16 with a terminating null, in a buffer of the [UnrealAllocator](crate::UnrealAllocator).
+    fn from(value: &str) -> Self {
+        FString {
+            data: value.encode_utf16().chain([0]).collect(),
+        }
+    }
+}
+
 impl Display for FText {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         unsafe { write!(f, "{}", self.text_data.as_ref().map(|it| it.text_source.to_string()).unwrap_or_default()) }
@@ -5,7 +5,7 @@ use tracing::{error, info, warn};
 use manasdk::ai_module::AAIController;
 use manasdk::engine::{AController, APawn, APlayerController, APlayerState, UGameplayStatics, UWorld};
 use manasdk::py_char_base::APyCharBase;
-use manasdk::{AsObjectPointer, HasClassObject, UObjectPointer};
+use manasdk::{AsObjectPointer, HasClassObject, TWeakObjectPtr, UObjectPointer};
 use manasdk::engine_settings::{ETwoPlayerSplitScreenType, UGameMapsSettings};
 use manasdk::x21::{AACTPlayerController, AActAIController, AActGameState, ACharacterBase, UActUIFunctionLibrary, USakuraBlueprintFunctionLibrary};
 use manasdk::x21_game_mode::APyX21GameMode;
@@ -23,13 +23,14 @@ pub struct ControlManager {
 }
 
 
+/// Weakly references the actors involved, which are destroyed on map loads.
 #[derive(Debug)]
 pub struct Claim {
     player_id: u8,
     hero_id: String,
-    character: Option<UObjectPointer<APyCharBase>>,
-    player_controller: UObjectPointer<AACTPlayerController>,
-    ai_controller: UObjectPointer<AActAIController>,
+    character: Option<TWeakObjectPtr<UObjectPointer<APyCharBase>>>,
+    player_controller: TWeakObjectPtr<UObjectPointer<AACTPlayerController>>,
+    ai_controller: TWeakObjectPtr<UObjectPointer<AActAIController>>,
 }
 
 #[derive(Debug)]
@@ -43,8 +44,8 @@ impl Claim {
         ClaimRef { player_id: self.player_id, hero_id: self.hero_id.clone() }
     }
 
-    pub fn character(&self) -> &Option<UObjectPointer<APyCharBase>> {
-        &self.character
+    pub fn character(&self) -> Option<&APyCharBase> {
+        self.character.as_ref()?.get()
     }
 
     pub fn hero_id(&self) -> &str {
@@ -95,9 +96,8 @@ impl ControlManager {
     pub fn claim(&self, player_id: u8, player_controller: UObjectPointer<AACTPlayerController>) -> Option<Claim> {
         let mut state = self.state.write().ok()?;
 
-        if player_controller.as_ref().take_if(|it| !it.b_forbid_setting_rotation_from_pawn).is_none() {
-            return None;
-        }
+        let player_controller = player_controller.as_ref().take_if(|it| !it.b_forbid_setting_rotation_from_pawn)?;
+        let player_controller = TWeakObjectPtr::new(player_controller).with_log_if_none("Unable to weakly reference the player controller!")?;
 
         // Check if claim already exists
         if state.active_claims.values().any(|it| it.player_id == player_id) {
@@ -106,19 +106,30 @@ impl ControlManager {
         }
 
         for member in self.get_available_members() {
-            let hero_id = member.get_hero_id().to_string().unwrap_or_default();
+            let hero_id = match member.try_get_hero_id() {
+                Ok(hero_id) => hero_id.to_string().unwrap_or_default(),
+                Err(err) => {
+                    warn!("Unable to identify team member: {err}");
+                    continue;
+                }
+            };
 
             // If no one else has claimed this hero...
             if state.active_claims.get(&hero_id).is_none() {
                 // ...and the hero is  ai-controlled...
                 if let Some(ai_controller) = member.controller.as_ref()?.cast::<AActAIController>() {
+                    let (Some(ai_controller), Some(character)) = (TWeakObjectPtr::new(ai_controller), TWeakObjectPtr::new(&*member)) else {
+                        warn!("Unable to weakly reference {hero_id}");
+                        continue;
+                    };
+
                     // ...give it to the claimer!
                     let claim = Claim {
                         player_id,
                         hero_id: hero_id.clone(),
-                        character: if state.enabled { Some(member.deref().into()) } else { None },
+                        character: if state.enabled { Some(character) } else { None },
                         player_controller,
-                        ai_controller: ai_controller.into(),
+                        ai_controller,
                     };
 
                     info!("Player {} claimed character {}", claim.player_id, claim.hero_id);
@@ -139,15 +150,19 @@ impl ControlManager {
     /// Renews a claim to a character.
     pub fn renew_claim(&self, claim: Claim) -> Option<Claim> {
         if let Some(mut claim) = self.sanity_check(claim) {
-            let matching_member = self.get_available_members().into_iter().find(|it| it.get_hero_id().to_string().unwrap_or_default() == claim.hero_id);
+            let matching_member = self.get_available_members().into_iter().find(|it| it.try_get_hero_id().is_ok_and(|hero_id| hero_id.to_string().unwrap_or_default() == claim.hero_id));
             if let Some(member) = matching_member {
                 // Only keep a reference to the character when we're enabled
                 if self.state.read().map(|it| it.enabled).unwrap_or_default() {
-                    claim.character = Some(member.into());
+                    if let Some(character) = TWeakObjectPtr::new(&*member) {
+                        claim.character = Some(character);
 
-                    self.ensure_claim(&claim, member);
+                        self.ensure_claim(&claim, member);
+                    } else {
+                        warn!("Unable to weakly reference {}", claim.hero_id);
+                    }
                 } else {
-                    if let Some(character) = claim.character.take().and_then(|it| it.try_get().ok()) {
+                    if let Some(character) = claim.character.take().and_then(|it| it.get()) {
                         self.ensure_evicted(&claim, character);
                     }
                 }
@@ -170,7 +185,7 @@ impl ControlManager {
         let mut state = self.state.write().expect("Unable to write to state");
         state.active_claims.remove(&claim.hero_id);
 
-        if let Some(character) = claim.character.clone().and_then(|it| it.try_get().ok()) {
+        if let Some(character) = claim.character() {
             self.ensure_evicted(&claim, character);
         } else {
             info!("...but they are not in control of any?");
@@ -178,9 +193,9 @@ impl ControlManager {
     }
 
     fn sanity_check(&self, claim: Claim) -> Option<Claim> {
-        if let (Some(expected_character), Some(player_controller)) = (&claim.character, claim.player_controller.as_ref()) {
+        if let (Some(expected_character), Some(player_controller)) = (claim.character(), claim.player_controller.get()) {
             let actual_character = &player_controller.pawn;
-            if !expected_character.is_same(actual_character) && actual_character.as_ref().is_some() {
+            if !UObjectPointer::from(expected_character).is_same(actual_character) && actual_character.as_ref().is_some() {
                 // Not controlling who we are supposed to be controlling!
 
                 if let Some(actual_character_ref) = actual_character.as_ref().and_then(|it| it.cast::<APyCharBase>()) {
@@ -204,15 +219,17 @@ impl ControlManager {
         }
 
         let mut player_controller_ref = claim.player_controller.clone();
-        let player_controller = player_controller_ref.as_mut()?;
+        let player_controller = player_controller_ref.get_mut()?;
         self.transfer_control(character, player_controller, |it| it.as_ref().map(|it| !it.is_player_controller()).unwrap_or_default())
     }
 
     fn ensure_evicted(&self, claim: &Claim, character: &APyCharBase) -> Option<()> {
         let mut ai_controller_ref = claim.ai_controller.clone();
-        let ai_controller = ai_controller_ref.as_mut().take_if(|it| it.is_valid()).with_log_if_none("No AI controller found in claim!")?;
+        let ai_controller = ai_controller_ref.get_mut().take_if(|it| it.is_valid()).with_log_if_none("No AI controller found in claim!")?;
 
-        self.transfer_control(character, ai_controller, |it| it.is_same(&claim.player_controller))
+        self.transfer_control(character, ai_controller, |it| {
+            claim.player_controller.get().is_some_and(|player_controller| it.is_same(&UObjectPointer::from(player_controller)))
+        })
     }
 
     fn transfer_control(&self, next_target: &APyCharBase, target_controller: &mut AController, source_controller_check: impl FnOnce(UObjectPointer<AController>) -> bool) -> Option<()> {
@@ -232,14 +249,20 @@ impl ControlManager {
                     next_target_state.name().to_string()
                 );
 
-                USakuraBlueprintFunctionLibrary::exchange_player_state_unique_id(
+                USakuraBlueprintFunctionLibrary::try_exchange_player_state_unique_id(
                     curr_target_state,
                     next_target_state,
-                );
+                )
+                .inspect_err(|err| error!("Unable to exchange player states: {err}"))
+                .ok()?;
 
                 let other_controller = next_target.controller.clone();
                 target_controller.player_state = next_target_state_ref.clone();
-                target_controller.possess(next_target);
+                if let Err(err) = target_controller.try_possess(next_target) {
+                    error!("Unable to possess character: {err}");
+                    target_controller.player_state = curr_target_state_ref.clone();
+                    return None;
+                }
 
                 if let Some(ctrl) = other_controller.clone().try_get().ok() {
                     ctrl.player_state = curr_target_state_ref.clone();
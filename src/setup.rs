@@ -2,6 +2,7 @@ use crate::statics::{MESSAGE_BUS, MODS, WORKER_QUEUE};
 use crate::utils::TrampolineWrapper;
 use anyhow::{anyhow, Context};
 use libmem::Address;
+use manasdk::{FMalloc, GMalloc};
 use std::ffi::c_void;
 use std::sync::OnceLock;
 use std::thread::sleep;
@@ -60,6 +61,25 @@ pub fn setup() -> anyhow::Result<()> {
 
     info!("Looking into module {}", module.name);
 
+    // FMemory::Free, which loads GMalloc with a RIP-relative `mov rcx, [GMalloc]` at offset 0xD
+    match unsafe {
+        libmem::sig_scan(
+            "48 85 C9 74 ?? 53 48 83 EC 20 48 8B D9 48 8B 0D ?? ?? ?? ??",
+            module.base,
+            module.size,
+        )
+    } {
+        Some(free_ptr) => {
+            let instruction = free_ptr + 0xD;
+            let displacement = unsafe { ((instruction + 3) as *const i32).read_unaligned() };
+            let gmalloc = (instruction + 7).wrapping_add_signed(displacement as isize);
+            info!("GMalloc Address=0x{:x}", gmalloc);
+
+            manasdk::set_allocator(unsafe { GMalloc::new(gmalloc as *const *mut FMalloc) });
+        }
+        None => error!("FMemory::Free not found, FString and TArray cannot be allocated"),
+    }
+
     let tick_ptr = unsafe {
         libmem::sig_scan(
             "48 89 4C 24 08 55 53 56 57 41 54 41 55 41 56 41 57 48 8D AC 24 ?? ?? ?? ?? 48 81 EC ?? ?? ?? ?? 83 3D",
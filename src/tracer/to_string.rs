@@ -1,6 +1,6 @@
 use either::Either;
 use flagset::FlagSet;
-use manasdk::{EClassCastFlags, FProperty, FStructProperty, UObject};
+use manasdk::{EClassCastFlags, FProperty, FStructProperty, TArray, TMap, TSet, UObject};
 use std::ffi::c_void;
 use manasdk::core_u_object::{FRotator, FVector, FVector2D, UStruct, UProperty, UStructProperty, UScriptStruct};
 
@@ -110,8 +110,8 @@ fn to_string_internal(prop: Either<&UProperty, &FProperty>, result: *const c_voi
                     }
                 }
                 EClassCastFlags::ArrayProperty => {
-                    // Handle ArrayProperty casting and conversion logic
-                    "ArrayProperty".to_string()
+                    // The element type isn't known here, but the length doesn't depend on it
+                    format!("Array ({} elements)", (*(result as *const TArray<u8>)).len())
                 }
                 EClassCastFlags::Int64Property => {
                     (*(result as *const i64)).to_string()
@@ -127,12 +127,10 @@ fn to_string_internal(prop: Either<&UProperty, &FProperty>, result: *const c_voi
                     (*(result as *const f64)).to_string()
                 }
                 EClassCastFlags::MapProperty => {
-                    // Handle MapProperty casting and conversion logic
-                    "MapProperty".to_string()
+                    format!("Map ({} entries)", (*(result as *const TMap<u8, u8>)).len())
                 }
                 EClassCastFlags::SetProperty => {
-                    // Handle SetProperty casting and conversion logic
-                    "SetProperty".to_string()
+                    format!("Set ({} elements)", (*(result as *const TSet<u8>)).len())
                 }
                 EClassCastFlags::EnumProperty => {
                     // Handle EnumProperty casting and conversion logic